@@ -27,7 +27,7 @@ fn main() -> ! {
     let mut handle = swm.handle.enable(&mut syscon.handle); // SWM isn't enabled by default on LPC845.
 
     // Set baud rate to 115200 baud
-    let clock_config = usart::Clock::new_with_baudrate(115200);
+    let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
 
     let tx_pin = p.pins.pio0_25.into_swm_pin();
     let rx_pin = p.pins.pio0_24.into_swm_pin();
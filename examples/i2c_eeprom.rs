@@ -48,7 +48,7 @@ fn main() -> ! {
     };
     #[cfg(feature = "845")]
     // Set baud rate to 115200 baud
-    let clock_config = usart::Clock::new_with_baudrate(115200);
+    let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     #[cfg(feature = "82x")]
     let tx_pin = p.pins.pio0_7.into_swm_pin();
     #[cfg(feature = "82x")]
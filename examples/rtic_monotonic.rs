@@ -0,0 +1,57 @@
+#![no_main]
+#![no_std]
+
+extern crate panic_rtt_target;
+
+#[rtic::app(device = lpc8xx_hal::pac, peripherals = false, dispatchers = [PIN_INT7_USART4])]
+mod app {
+    use lpc8xx_hal::{
+        gpio::{direction::Output, GpioPin, Level},
+        mrt::{self, monotonic::Duration},
+        pins::PIO1_1,
+        Peripherals,
+    };
+
+    #[monotonic(binds = MRT0, default = true)]
+    type Mono = mrt::monotonic::Monotonic<mrt::MRT0, mrt::MRT1>;
+
+    #[shared]
+    struct Shared {}
+
+    #[local]
+    struct Local {
+        led: GpioPin<PIO1_1, Output>,
+    }
+
+    #[init]
+    fn init(_: init::Context) -> (Shared, Local, init::Monotonics) {
+        rtt_target::rtt_init_print!();
+
+        let p = Peripherals::take().unwrap();
+
+        let mut syscon = p.SYSCON.split();
+        let gpio = p.GPIO.enable(&mut syscon.handle);
+        let mrt = p.MRT0.split(&mut syscon.handle);
+
+        let led = p
+            .pins
+            .pio1_1
+            .into_output_pin(gpio.tokens.pio1_1, Level::Low);
+
+        let mono = mrt::monotonic::Monotonic::new(
+            mrt.mrt0,
+            mrt.mrt1,
+            &syscon.system_clock,
+        );
+
+        blink::spawn().unwrap();
+
+        (Shared {}, Local { led }, init::Monotonics(mono))
+    }
+
+    #[task(local = [led])]
+    fn blink(cx: blink::Context) {
+        cx.local.led.toggle();
+        blink::spawn_after(Duration::millis(500)).unwrap();
+    }
+}
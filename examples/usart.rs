@@ -53,7 +53,7 @@ fn main() -> ! {
 
     #[cfg(feature = "845")]
     // Set baud rate to 115200 baud
-    let clock_config = usart::Clock::new_with_baudrate(115200);
+    let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
 
     // Make the rx & tx pins available to the switch matrix API, by changing
     // their state using `into_swm_pin`. This is required, because we're going
@@ -17,7 +17,7 @@ fn main() -> ! {
     let dma = p.DMA.enable(&mut syscon.handle);
     let mut swm_handle = swm.handle.enable(&mut syscon.handle);
 
-    let clock_config = usart::Clock::new_with_baudrate(115200);
+    let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
 
     let (u0_rxd, _) = swm
         .movable_functions
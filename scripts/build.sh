@@ -52,6 +52,21 @@ function build() {
     cargo build --verbose --features=$TARGET-rt,no-target-warning --examples
     cargo doc --features=$TARGET-rt,no-target-warning
 
+    # Build with optional features that are available on all targets
+    cargo build --verbose --features=$TARGET,no-target-warning,defmt
+
+    # Build and test optional features that are only available on some targets
+    if [ "$TARGET" = 845 ]; then
+        cargo test \
+            --verbose \
+            --features=$TARGET,no-target-warning,rtic-monotonic \
+            --target=$HOST_TARGET
+        cargo build \
+            --verbose \
+            --features=$TARGET-rt,no-target-warning,rtic-monotonic \
+            --examples
+    fi
+
     # Build test suite
     (
         cd test-suite
@@ -0,0 +1,544 @@
+//! Debounced buttons
+//!
+//! This module provides a debouncing state machine, [`Debouncer`], which turns
+//! the raw edges of a button input into debounced press, release, long-press,
+//! and double-click [`Event`]s. The state machine doesn't access any hardware.
+//! It is driven by the caller, who reports edges and expired timeouts, and
+//! starts a timer for the timeouts it requests.
+//!
+//! On LPC845, [`Button`] combines the state machine with a GPIO input, its pin
+//! interrupt, and an MRT channel that times the debounce window. No polling is
+//! required: the driver only does work in the pin interrupt and MRT interrupt
+//! handlers.
+//!
+//! # Example
+//!
+//! Driving the state machine by hand:
+//!
+//! ```
+//! use lpc8xx_hal::button::{Debouncer, Event, Timing};
+//!
+//! let mut debouncer = Debouncer::new(Timing::default());
+//!
+//! // The button is pressed and bounces. Every edge restarts the debounce
+//! // window.
+//! assert_eq!(debouncer.edge(0), 20);
+//! assert_eq!(debouncer.edge(5), 20);
+//!
+//! // The debounce window expires with the button pressed.
+//! let output = debouncer.timeout(true);
+//! assert_eq!(output.event, Some(Event::Press));
+//!
+//! // The button is held until the long-press time expires.
+//! assert_eq!(output.timeout, Some(1000));
+//! let output = debouncer.timeout(true);
+//! assert_eq!(output.event, Some(Event::LongPress));
+//! assert_eq!(output.timeout, None);
+//! ```
+//!
+//! Using the driver on LPC845:
+//!
+//! ``` no_run
+//! # #[cfg(feature = "845")]
+//! # {
+//! use lpc8xx_hal::{
+//!     button::{Button, Event, Timing},
+//!     gpio::Level,
+//!     Peripherals,
+//! };
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let gpio = p.GPIO.enable(&mut syscon.handle);
+//! let pinint = p.PININT.enable(&mut syscon.handle);
+//! let mrt = p.MRT0.split(&mut syscon.handle);
+//!
+//! let pin = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+//! let interrupt = pinint
+//!     .interrupts
+//!     .pinint0
+//!     .select(pin.inner(), &mut syscon.handle);
+//!
+//! // The button pulls the pin LOW, when pressed.
+//! let mut button = Button::new(
+//!     pin,
+//!     interrupt,
+//!     mrt.mrt0,
+//!     &syscon.system_clock,
+//!     Timing::default(),
+//!     Level::Low,
+//! );
+//!
+//! // In the PININT0 interrupt handler
+//! if let Some(event) = button.on_pin_interrupt() {
+//!     // handle event
+//! }
+//!
+//! // In the MRT0 interrupt handler
+//! if let Some(Event::LongPress) = button.on_timer_interrupt() {
+//!     // handle long press
+//! }
+//! # }
+//! ```
+//!
+//! [`Debouncer`]: struct.Debouncer.html
+//! [`Event`]: enum.Event.html
+//! [`Button`]: struct.Button.html
+
+#[cfg(feature = "845")]
+use core::convert::TryFrom;
+
+#[cfg(feature = "845")]
+use crate::{
+    clock::Frequency as _,
+    gpio::{direction, GpioPin, Level},
+    init_state::Enabled,
+    mrt::{self, Ticks},
+    pinint, pins, syscon,
+};
+
+/// An event generated by a debounced button
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// The button has been pressed
+    Press,
+
+    /// The button has been released
+    Release,
+
+    /// The button has been held for the long-press time
+    ///
+    /// Generated once per press, in addition to [`Event::Press`] and
+    /// [`Event::Release`].
+    ///
+    /// [`Event::Press`]: #variant.Press
+    /// [`Event::Release`]: #variant.Release
+    LongPress,
+
+    /// The button has been pressed within the double-click time after a
+    /// release
+    ///
+    /// Generated instead of [`Event::Press`], for the second press.
+    ///
+    /// [`Event::Press`]: #variant.Press
+    DoubleClick,
+}
+
+/// The timing parameters of a [`Debouncer`], in milliseconds
+///
+/// [`Debouncer`]: struct.Debouncer.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Timing {
+    /// The time the input has to be stable, before a change is accepted
+    pub debounce: u32,
+
+    /// The time a button has to be held, to generate a long press
+    pub long_press: u32,
+
+    /// The maximum time between a release and the next press, for that press
+    /// to count as a double click
+    pub double_click: u32,
+}
+
+impl Default for Timing {
+    /// 20 ms debounce time, 1 s long-press time, 300 ms double-click time
+    fn default() -> Self {
+        Self {
+            debounce: 20,
+            long_press: 1000,
+            double_click: 300,
+        }
+    }
+}
+
+/// The result of [`Debouncer::timeout`]
+///
+/// [`Debouncer::timeout`]: struct.Debouncer.html#method.timeout
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Output {
+    /// The event generated, if any
+    pub event: Option<Event>,
+
+    /// The next timeout to start, in milliseconds, if any
+    pub timeout: Option<u32>,
+}
+
+/// Debouncing state machine for a button
+///
+/// The state machine is driven by two methods:
+///
+/// - [`Debouncer::edge`] must be called for every edge of the raw input.
+/// - [`Debouncer::timeout`] must be called, when the last timeout it requested
+///   has expired.
+///
+/// Both methods return the timeout to start next. Starting a new timeout
+/// cancels the previous one.
+///
+/// ```
+/// use lpc8xx_hal::button::{Debouncer, Event, Timing};
+///
+/// let mut debouncer = Debouncer::new(Timing::default());
+///
+/// // First click
+/// debouncer.edge(0);
+/// assert_eq!(debouncer.timeout(true).event, Some(Event::Press));
+/// debouncer.edge(100);
+/// assert_eq!(debouncer.timeout(false).event, Some(Event::Release));
+///
+/// // A bounce that doesn't change the level
+/// debouncer.edge(30);
+/// assert_eq!(debouncer.timeout(false).event, None);
+///
+/// // Second press, within the double-click time
+/// debouncer.edge(50);
+/// assert_eq!(debouncer.timeout(true).event, Some(Event::DoubleClick));
+/// assert!(debouncer.is_pressed());
+/// ```
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// [`Debouncer::edge`]: #method.edge
+/// [`Debouncer::timeout`]: #method.timeout
+/// [module documentation]: index.html
+#[derive(Clone, Debug)]
+pub struct Debouncer {
+    timing: Timing,
+    state: State,
+    debouncing: bool,
+    pending: u32,
+}
+
+impl Debouncer {
+    /// Create a new state machine, with the button released
+    pub fn new(timing: Timing) -> Self {
+        Self {
+            timing,
+            state: State::Released { since: None },
+            debouncing: false,
+            pending: 0,
+        }
+    }
+
+    /// Returns the timing parameters
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    /// Indicates whether the debounced button is currently pressed
+    pub fn is_pressed(&self) -> bool {
+        matches!(self.state, State::Pressed { .. })
+    }
+
+    /// Report an edge of the raw input
+    ///
+    /// `elapsed` is the time in milliseconds since the last requested timeout
+    /// was started, or zero, if no timeout is running.
+    ///
+    /// Returns the timeout to start, which is always the debounce time.
+    pub fn edge(&mut self, elapsed: u32) -> u32 {
+        self.advance(elapsed);
+
+        self.debouncing = true;
+        self.request(self.timing.debounce)
+    }
+
+    /// Report that the last requested timeout has expired
+    ///
+    /// `pressed` is the current level of the raw input.
+    pub fn timeout(&mut self, pressed: bool) -> Output {
+        self.advance(self.pending);
+        self.pending = 0;
+
+        let event = if self.debouncing {
+            self.debouncing = false;
+            self.settle(pressed)
+        } else {
+            self.expire()
+        };
+
+        let timeout = self.next_timeout().map(|timeout| self.request(timeout));
+
+        Output { event, timeout }
+    }
+
+    /// Accept the input level at the end of the debounce window
+    fn settle(&mut self, pressed: bool) -> Option<Event> {
+        match (self.state, pressed) {
+            (State::Released { since }, true) => {
+                let double_click = match since {
+                    Some(since) => since <= self.timing.double_click,
+                    None => false,
+                };
+
+                self.state = State::Pressed {
+                    held: 0,
+                    long_press_reported: false,
+                    double_click,
+                };
+
+                if double_click {
+                    Some(Event::DoubleClick)
+                } else {
+                    Some(Event::Press)
+                }
+            }
+            (State::Pressed { double_click, .. }, false) => {
+                // A press that completed a double click can't start another
+                // one.
+                let since = if double_click { None } else { Some(0) };
+                self.state = State::Released { since };
+
+                Some(Event::Release)
+            }
+            // The input bounced, but didn't change.
+            _ => None,
+        }
+    }
+
+    /// Handle the expiration of the long-press or double-click time
+    fn expire(&mut self) -> Option<Event> {
+        let timing = self.timing;
+
+        match &mut self.state {
+            State::Pressed {
+                held,
+                long_press_reported,
+                ..
+            } if !*long_press_reported && *held >= timing.long_press => {
+                *long_press_reported = true;
+                Some(Event::LongPress)
+            }
+            State::Released { since }
+                if since.is_some_and(|since| since > timing.double_click) =>
+            {
+                *since = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn next_timeout(&self) -> Option<u32> {
+        match self.state {
+            State::Pressed {
+                held,
+                long_press_reported: false,
+                ..
+            } => Some(self.timing.long_press.saturating_sub(held).max(1)),
+            State::Released { since: Some(since) } => {
+                // Expire the double-click window one millisecond after its end,
+                // as a press right at the end still counts.
+                Some(
+                    (self.timing.double_click + 1).saturating_sub(since).max(1),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    fn advance(&mut self, elapsed: u32) {
+        match &mut self.state {
+            State::Pressed { held, .. } => {
+                *held = held.saturating_add(elapsed);
+            }
+            State::Released { since: Some(since) } => {
+                *since = since.saturating_add(elapsed);
+            }
+            State::Released { since: None } => {}
+        }
+    }
+
+    fn request(&mut self, timeout: u32) -> u32 {
+        self.pending = timeout;
+        timeout
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Released {
+        /// Time since the release, while the double-click window is open
+        since: Option<u32>,
+    },
+    Pressed {
+        held: u32,
+        long_press_reported: bool,
+        double_click: bool,
+    },
+}
+
+/// A debounced button, driven by a pin interrupt and an MRT channel
+///
+/// Combines a GPIO input, the pin interrupt that has been selected for it, and
+/// an MRT channel, with a [`Debouncer`]. The pin interrupt is configured to
+/// fire on both edges, and the MRT channel times the debounce window, as well
+/// as the long-press and double-click times.
+///
+/// [`Button::on_pin_interrupt`] must be called from the pin interrupt handler,
+/// [`Button::on_timer_interrupt`] from the MRT interrupt handler. Both return
+/// the events generated by the button.
+///
+/// Timings are converted to MRT ticks using the frequency of the system clock
+/// that is passed to [`Button::new`]. They are limited to the maximum duration
+/// of the MRT, which is roughly 178 seconds at 12 MHz.
+///
+/// Only available on LPC845.
+///
+/// [`Debouncer`]: struct.Debouncer.html
+/// [`Button::new`]: #method.new
+/// [`Button::on_pin_interrupt`]: #method.on_pin_interrupt
+/// [`Button::on_timer_interrupt`]: #method.on_timer_interrupt
+#[cfg(feature = "845")]
+pub struct Button<I, P, T>
+where
+    T: mrt::Trait,
+{
+    pin: GpioPin<P, direction::Input>,
+    interrupt: pinint::Interrupt<I, P, Enabled>,
+    timer: mrt::Channel<T, mrt::mode::OneShot>,
+    debouncer: Debouncer,
+    active: Level,
+    armed: u32,
+    clock_hz: u32,
+}
+
+#[cfg(feature = "845")]
+impl<I, P, T> Button<I, P, T>
+where
+    I: pinint::Trait,
+    P: pins::Trait,
+    T: mrt::Trait,
+{
+    /// Create a new button
+    ///
+    /// `active` is the level of the pin, while the button is pressed. The
+    /// button is assumed to be released initially.
+    ///
+    /// The frequency of the system clock that the MRT runs on is read from
+    /// `clock` once, so the button needs to be created again, if the system
+    /// clock configuration changes.
+    ///
+    /// Enables both edges of the pin interrupt, and the interrupt of the MRT
+    /// channel. The interrupts still need to be unmasked in the NVIC.
+    pub fn new(
+        pin: GpioPin<P, direction::Input>,
+        mut interrupt: pinint::Interrupt<I, P, Enabled>,
+        timer: mrt::Channel<T>,
+        clock: &syscon::SystemClock,
+        timing: Timing,
+        active: Level,
+    ) -> Self {
+        interrupt.enable_rising_edge();
+        interrupt.enable_falling_edge();
+
+        let mut timer = timer.into_one_shot();
+        timer.enable_interrupt();
+
+        Self {
+            pin,
+            interrupt,
+            timer,
+            debouncer: Debouncer::new(timing),
+            active,
+            armed: 0,
+            clock_hz: clock.hz(),
+        }
+    }
+
+    /// Handle the pin interrupt
+    ///
+    /// Must be called from the interrupt handler of the pin interrupt. Clears
+    /// the edge flags, and restarts the debounce window.
+    ///
+    /// Returns an event, if the MRT channel had expired, but its interrupt had
+    /// not been handled yet.
+    pub fn on_pin_interrupt(&mut self) -> Option<Event> {
+        // Handle an expired timeout first, so the time that has elapsed since
+        // it was started isn't accounted for twice.
+        let event = self.on_timer_interrupt();
+
+        let rising = self.interrupt.clear_rising_edge_flag();
+        let falling = self.interrupt.clear_falling_edge_flag();
+
+        if rising || falling {
+            let elapsed = if self.timer.is_running() {
+                self.armed.saturating_sub(self.timer.value())
+            } else {
+                0
+            };
+
+            let timeout =
+                self.debouncer.edge(ticks_to_ms(elapsed, self.clock_hz));
+            self.start(timeout);
+        }
+
+        event
+    }
+
+    /// Handle the MRT interrupt
+    ///
+    /// Must be called from the MRT interrupt handler. Does nothing, if the
+    /// interrupt of this button's MRT channel is not pending, so it is safe to
+    /// call, if the MRT interrupt is shared with other channels.
+    pub fn on_timer_interrupt(&mut self) -> Option<Event> {
+        if !self.timer.is_interrupt_pending() {
+            return None;
+        }
+        self.timer.clear_interrupt();
+        self.armed = 0;
+
+        let pressed = match self.active {
+            Level::High => self.pin.is_high(),
+            Level::Low => self.pin.is_low(),
+        };
+
+        let output = self.debouncer.timeout(pressed);
+        if let Some(timeout) = output.timeout {
+            self.start(timeout);
+        }
+
+        output.event
+    }
+
+    /// Indicates whether the button is currently pressed, after debouncing
+    pub fn is_pressed(&self) -> bool {
+        self.debouncer.is_pressed()
+    }
+
+    /// Release the pin, the pin interrupt, and the MRT channel
+    ///
+    /// The interrupts are disabled.
+    pub fn free(
+        mut self,
+    ) -> (
+        GpioPin<P, direction::Input>,
+        pinint::Interrupt<I, P, Enabled>,
+        mrt::Channel<T>,
+    ) {
+        self.interrupt.disable_rising_edge();
+        self.interrupt.disable_falling_edge();
+        self.timer.disable_interrupt();
+
+        (self.pin, self.interrupt, self.timer.into_repeat())
+    }
+
+    fn start(&mut self, ms: u32) {
+        let ticks = ms_to_ticks(ms, self.clock_hz);
+        let ticks = Ticks::try_from(ticks).unwrap_or(mrt::MAX_VALUE);
+
+        self.armed = ticks.to_u32();
+        self.timer.start(ticks);
+    }
+}
+
+#[cfg(feature = "845")]
+fn ms_to_ticks(ms: u32, clock_hz: u32) -> u32 {
+    let ticks = ms as u64 * clock_hz as u64 / 1_000;
+    ticks.min(u32::MAX as u64) as u32
+}
+
+#[cfg(feature = "845")]
+fn ticks_to_ms(ticks: u32, clock_hz: u32) -> u32 {
+    (ticks as u64 * 1_000 / clock_hz as u64) as u32
+}
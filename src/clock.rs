@@ -53,6 +53,12 @@ pub trait Frequency {
     fn hz(&self) -> u32;
 }
 
+/// Compute the deviation of `actual` from `target` in parts per million
+pub(crate) fn error_ppm(actual: u32, target: u32) -> i32 {
+    let deviation = i64::from(actual) - i64::from(target);
+    (deviation * 1_000_000 / i64::from(target)) as i32
+}
+
 /// Marker trait that identifies a clock as currently being enabled
 ///
 /// A clock that is always enabled can just implement this trait
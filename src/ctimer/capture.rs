@@ -0,0 +1,313 @@
+//! Contains types related to CTIMER input capture
+//!
+//! A capture channel latches the value of the timer counter, whenever a
+//! selected edge occurs on its input. Capture inputs are assigned to pins via
+//! the switch matrix (`T0_CAP0` to `T0_CAP2`) and attached to the CTIMER using
+//! [`CTIMER::capture`].
+//!
+//! The timer counter is reset by the match that defines the PWM period (see
+//! [`CTIMER::enable`]), so captured values are always in the range from zero
+//! to that period. The helpers in this module take this into account, when
+//! computing the number of ticks between two captures. To measure slow signals
+//! with the best resolution, enable the CTIMER with a period of `u32::MAX`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use lpc8xx_hal::{
+//!     ctimer::capture::Edge,
+//!     Peripherals,
+//! };
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let swm = p.SWM.split();
+//! let mut syscon = p.SYSCON.split();
+//!
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! let input = p.pins.pio0_16.into_swm_pin();
+//! let (t0_cap0, _) = swm.movable_functions.t0_cap0.assign(
+//!     input,
+//!     &mut swm_handle,
+//! );
+//!
+//! // Free-running timer at the main clock frequency (12 MHz by default)
+//! let mut ctimer = p.CTIMER0.enable(u32::MAX, 0, &mut syscon.handle);
+//!
+//! let mut capture = ctimer.capture(t0_cap0, Edge::Rising);
+//!
+//! let period = capture.measure_period();
+//! let frequency = capture.frequency(12_000_000);
+//! ```
+//!
+//! [`CTIMER::capture`]: ../struct.CTIMER.html#method.capture
+//! [`CTIMER::enable`]: ../struct.CTIMER.html#method.enable
+
+use core::{fmt, marker::PhantomData};
+
+use cortex_m::interrupt;
+use void::Void;
+
+use crate::{pac::CTIMER0, swm};
+
+use super::period::{self, MR3};
+
+/// A CTIMER capture channel
+///
+/// Can be created using [`CTIMER::capture`]. The type parameter `T` identifies
+/// the SWM function that feeds this channel. `Period` identifies the match
+/// register that defines the PWM period (see [`period`]).
+///
+/// [`CTIMER::capture`]: ../struct.CTIMER.html#method.capture
+/// [`period`]: ../period/index.html
+pub struct Capture<T, Period = MR3> {
+    _channel: PhantomData<T>,
+    _period: PhantomData<Period>,
+}
+
+impl<T, Period> Capture<T, Period>
+where
+    T: Trait,
+    Period: period::Trait,
+{
+    pub(super) fn new(edge: Edge) -> Self {
+        let mut self_ = Self {
+            _channel: PhantomData,
+            _period: PhantomData,
+        };
+
+        self_.set_edge(edge);
+        self_.clear();
+
+        self_
+    }
+
+    /// Select the edge that triggers a capture
+    pub fn set_edge(&mut self, edge: Edge) {
+        let bits = match edge {
+            Edge::Rising => 0b01,
+            Edge::Falling => 0b10,
+            Edge::Both => 0b11,
+        };
+
+        self.modify_ccr(0b11, bits);
+    }
+
+    /// Enable the capture interrupt for this channel
+    ///
+    /// The interrupt is triggered whenever a capture takes place. It needs to
+    /// be cleared using [`Capture::clear`].
+    ///
+    /// [`Capture::clear`]: #method.clear
+    pub fn enable_interrupt(&mut self) {
+        self.modify_ccr(0b100, 0b100);
+    }
+
+    /// Disable the capture interrupt for this channel
+    pub fn disable_interrupt(&mut self) {
+        self.modify_ccr(0b100, 0b000);
+    }
+
+    /// Indicates whether a capture has taken place since the last call to
+    /// [`Capture::clear`]
+    ///
+    /// [`Capture::clear`]: #method.clear
+    pub fn is_captured(&self) -> bool {
+        // Sound, as we're only reading from a stateless register.
+        let ctimer = unsafe { &*CTIMER0::ptr() };
+        ctimer.ir.read().bits() & Self::IR_MASK != 0
+    }
+
+    /// Clear the capture flag (and interrupt) of this channel
+    pub fn clear(&mut self) {
+        // Sound, as writing zeros to IR has no effect, and we're only writing
+        // the bit that belongs to this channel.
+        let ctimer = unsafe { &*CTIMER0::ptr() };
+        ctimer.ir.write(|w| unsafe { w.bits(Self::IR_MASK) });
+    }
+
+    /// Return the value of the timer counter at the last capture
+    pub fn read(&self) -> u32 {
+        // Sound, as the capture register is exclusively read by this channel.
+        let ctimer = unsafe { &*CTIMER0::ptr() };
+        ctimer.cr[T::ID as usize].read().cap().bits()
+    }
+
+    /// Wait for the next capture
+    ///
+    /// Returns the captured timer value and clears the capture flag. Returns
+    /// `nb::Error::WouldBlock`, if no capture has taken place since the flag
+    /// was last cleared.
+    pub fn wait(&mut self) -> nb::Result<u32, Void> {
+        if !self.is_captured() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let value = self.read();
+        self.clear();
+
+        Ok(value)
+    }
+
+    /// Return the number of timer ticks between two captured values
+    ///
+    /// Takes into account that the timer counter wraps at the configured
+    /// period.
+    pub fn ticks_between(&self, first: u32, second: u32) -> u32 {
+        ticks_between(first, second, self.period())
+    }
+
+    /// Measure the period of the input signal in timer ticks
+    ///
+    /// Blocks until two consecutive edges, as selected by [`Capture::set_edge`],
+    /// have been captured. Any capture that happened before this method was
+    /// called is discarded.
+    ///
+    /// [`Capture::set_edge`]: #method.set_edge
+    pub fn measure_period(&mut self) -> u32 {
+        self.clear();
+
+        let first = nb::block!(self.wait()).unwrap();
+        let second = nb::block!(self.wait()).unwrap();
+
+        self.ticks_between(first, second)
+    }
+
+    /// Measure the width of a high pulse on the input in timer ticks
+    ///
+    /// Blocks until a rising edge, followed by a falling edge, has been
+    /// captured. The edge selection is switched in software between the two
+    /// edges, so pulses that are too short to be detected that way, will result
+    /// in the width of a later pulse being measured. The edge selection is set
+    /// to [`Edge::Rising`] when this method returns.
+    ///
+    /// [`Edge::Rising`]: enum.Edge.html#variant.Rising
+    pub fn measure_pulse_width(&mut self) -> u32 {
+        self.set_edge(Edge::Rising);
+        self.clear();
+        let rising = nb::block!(self.wait()).unwrap();
+
+        self.set_edge(Edge::Falling);
+        self.clear();
+        let falling = nb::block!(self.wait()).unwrap();
+
+        self.set_edge(Edge::Rising);
+
+        self.ticks_between(rising, falling)
+    }
+
+    /// Measure the frequency of the input signal in Hz
+    ///
+    /// Measures the period of the input signal, using
+    /// [`Capture::measure_period`], and converts it to a frequency, using the
+    /// frequency of the timer counter. The timer frequency is the frequency of
+    /// the main clock, divided by the prescaler value plus one.
+    ///
+    /// Please note that both edges count towards the period, if the edge
+    /// selection is set to [`Edge::Both`].
+    ///
+    /// [`Capture::measure_period`]: #method.measure_period
+    /// [`Edge::Both`]: enum.Edge.html#variant.Both
+    pub fn frequency(&mut self, timer_hz: u32) -> u32 {
+        match self.measure_period() {
+            0 => 0,
+            period => timer_hz / period,
+        }
+    }
+
+    fn period(&self) -> u32 {
+        // Sound, as we're only reading from the register.
+        let ctimer = unsafe { &*CTIMER0::ptr() };
+        ctimer.mr[Period::ID as usize].read().match_().bits()
+    }
+
+    fn modify_ccr(&mut self, mask: u32, bits: u32) {
+        let shift = T::ID as u32 * 3;
+
+        // Sound, as CCR is shared between all capture channels, but access to
+        // it is protected by a critical section, and we're only modifying the
+        // bits that belong to this channel.
+        let ctimer = unsafe { &*CTIMER0::ptr() };
+        interrupt::free(|_| {
+            ctimer.ccr.modify(|r, w| unsafe {
+                w.bits((r.bits() & !(mask << shift)) | (bits << shift))
+            })
+        });
+    }
+
+    const IR_MASK: u32 = 0x1 << (4 + T::ID);
+}
+
+impl<T, Period> fmt::Debug for Capture<T, Period>
+where
+    T: Trait,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Capture").field("id", &T::ID).finish()
+    }
+}
+
+/// Selects which edges of the input trigger a capture
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Edge {
+    /// Capture on rising edges
+    Rising,
+
+    /// Capture on falling edges
+    Falling,
+
+    /// Capture on both rising and falling edges
+    Both,
+}
+
+/// Return the number of timer ticks between two captured values
+///
+/// `period` is the value the timer counter is reset at, which is the period
+/// the CTIMER was enabled with. The counter counts from zero up to and
+/// including `period`, so if `second` is smaller than `first`, the counter is
+/// assumed to have wrapped once in between.
+///
+/// ```
+/// use lpc8xx_hal::ctimer::capture::ticks_between;
+///
+/// assert_eq!(ticks_between(100, 250, 999), 150);
+/// assert_eq!(ticks_between(900, 100, 999), 200);
+/// assert_eq!(ticks_between(u32::MAX, 9, u32::MAX), 10);
+/// ```
+pub fn ticks_between(first: u32, second: u32, period: u32) -> u32 {
+    if second >= first {
+        second - first
+    } else {
+        let modulus = period as u64 + 1;
+        (modulus - first as u64 + second as u64) as u32
+    }
+}
+
+/// Implemented for all SWM functions that feed a CTIMER capture channel
+pub trait Trait: private::Sealed {
+    /// Identifies the capture channel
+    const ID: u8;
+}
+
+macro_rules! capture_inputs {
+    ($($function:ident, $id:expr;)*) => {
+        $(
+            impl private::Sealed for swm::$function {}
+
+            impl Trait for swm::$function {
+                const ID: u8 = $id;
+            }
+        )*
+    };
+}
+
+capture_inputs! {
+    T0_CAP0, 0;
+    T0_CAP1, 1;
+    T0_CAP2, 2;
+}
+
+mod private {
+    pub trait Sealed {}
+}
@@ -16,16 +16,27 @@ use crate::{
 
 use self::state::Attached;
 
+use super::period::{self, MR3};
+
 /// A CTIMER PWM channel
-pub struct Channel<T, PeripheralState, State> {
+///
+/// The type parameter `Period` identifies the match register that defines the
+/// PWM period. Please refer to the [`period`] module for details.
+///
+/// [`period`]: ../period/index.html
+pub struct Channel<T, PeripheralState, State, Period = MR3> {
     mr: RegProxy<MR>,
     msr: RegProxy<MSR>,
     channel: PhantomData<T>,
     peripheral_state: PhantomData<PeripheralState>,
     _state: PhantomData<State>,
+    _period: PhantomData<Period>,
+    polarity: Polarity,
 }
 
-impl<T, PeripheralState, State> Channel<T, PeripheralState, State> {
+impl<T, PeripheralState, State, Period>
+    Channel<T, PeripheralState, State, Period>
+{
     pub(super) fn new() -> Self {
         Self {
             mr: RegProxy::new(),
@@ -33,13 +44,80 @@ impl<T, PeripheralState, State> Channel<T, PeripheralState, State> {
             channel: PhantomData,
             peripheral_state: PhantomData,
             _state: PhantomData,
+            _period: PhantomData,
+            polarity: Polarity::Normal,
+        }
+    }
+}
+
+impl<T, Period> Channel<T, Enabled, Attached, Period>
+where
+    T: Trait,
+    Period: period::Trait,
+{
+    /// Set the polarity of this channel
+    ///
+    /// The hardware doesn't support inverting the PWM output, so inverted
+    /// polarity is implemented by converting the duty cycle, whenever it is
+    /// written or read. The duty cycle that is currently configured is
+    /// preserved.
+    pub fn set_polarity(&mut self, polarity: Polarity) {
+        let duty = self.duty();
+        self.polarity = polarity;
+        self.set_duty_raw(duty);
+    }
+
+    /// Return the polarity of this channel
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    /// Set the alignment of this channel's pulse within the period
+    ///
+    /// This is a shortcut for [`Channel::set_polarity`], as the alignment is
+    /// tied to the polarity. Please refer to [`Alignment`] for details.
+    ///
+    /// [`Channel::set_polarity`]: #method.set_polarity
+    /// [`Alignment`]: enum.Alignment.html
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.set_polarity(alignment.into());
+    }
+
+    /// Return the alignment of this channel's pulse within the period
+    pub fn alignment(&self) -> Alignment {
+        self.polarity.alignment()
+    }
+
+    fn duty(&self) -> u32 {
+        let value = self.msr[Self::MR].read().match_shadow().bits();
+        self.convert(value)
+    }
+
+    fn set_duty_raw(&mut self, duty: u32) {
+        let value = self.convert(duty);
+        unsafe { self.msr[Self::MR].write(|w| w.match_shadow().bits(value)) };
+    }
+
+    fn period(&self) -> u32 {
+        self.mr[Period::ID as usize].read().match_().bits()
+    }
+
+    // Converting is its own inverse, so this is used in both directions.
+    fn convert(&self, value: u32) -> u32 {
+        match self.polarity {
+            Polarity::Normal => value,
+            Polarity::Inverted => self.period().saturating_sub(value),
         }
     }
+
+    /// The match register that drives this channel
+    const MR: usize = period::match_register::<Period>(T::ID);
 }
 
-impl<T> PwmPin for Channel<T, Enabled, Attached>
+impl<T, Period> PwmPin for Channel<T, Enabled, Attached, Period>
 where
     T: Trait,
+    Period: period::Trait,
 {
     type Duty = u32;
 
@@ -55,25 +133,24 @@ where
 
     /// Returns the current duty cycle
     fn get_duty(&self) -> Self::Duty {
-        self.msr[T::ID as usize].read().match_shadow().bits()
+        self.duty()
     }
 
     /// Returns the maximum duty cycle value
     fn get_max_duty(&self) -> Self::Duty {
-        self.mr[3].read().match_().bits()
+        self.period()
     }
 
     /// Sets a new duty cycle
     fn set_duty(&mut self, duty: Self::Duty) {
-        unsafe {
-            self.msr[T::ID as usize].write(|w| w.match_shadow().bits(duty))
-        };
+        self.set_duty_raw(duty);
     }
 }
 
-impl<T> PwmPinAlpha for Channel<T, Enabled, Attached>
+impl<T, Period> PwmPinAlpha for Channel<T, Enabled, Attached, Period>
 where
     T: Trait,
+    Period: period::Trait,
 {
     type Error = Infallible;
     type Duty = u32;
@@ -94,29 +171,109 @@ where
 
     /// Returns the current duty cycle
     fn get_duty(&self) -> Result<Self::Duty, Self::Error> {
-        Ok(self.msr[T::ID as usize].read().match_shadow().bits())
+        Ok(self.duty())
     }
 
     /// Returns the maximum duty cycle value
     fn get_max_duty(&self) -> Result<Self::Duty, Self::Error> {
-        Ok(self.mr[3].read().match_().bits())
+        Ok(self.period())
     }
 
     /// Sets a new duty cycle
     fn set_duty(&mut self, duty: Self::Duty) -> Result<(), Self::Error> {
-        unsafe {
-            Ok(self.msr[T::ID as usize].write(|w| w.match_shadow().bits(duty)))
+        self.set_duty_raw(duty);
+        Ok(())
+    }
+}
+
+/// The polarity of a PWM channel
+///
+/// Used with [`Channel::set_polarity`].
+///
+/// The hardware only supports edge-aligned PWM, where the output is low at the
+/// start of each period and goes high once the timer counter reaches the
+/// match register. Center-aligned PWM is not supported. The polarity selects
+/// both the active level and the alignment of the pulse that the duty cycle
+/// describes:
+///
+/// - [`Polarity::Normal`]: Active-low pulse, aligned to the start of the
+///   period (left-aligned)
+/// - [`Polarity::Inverted`]: Active-high pulse, aligned to the end of the
+///   period (right-aligned)
+///
+/// [`Channel::set_polarity`]: struct.Channel.html#method.set_polarity
+/// [`Polarity::Normal`]: #variant.Normal
+/// [`Polarity::Inverted`]: #variant.Inverted
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Polarity {
+    /// The duty cycle is written to the match register unchanged
+    ///
+    /// The output is low for the duration of the duty cycle at the start of
+    /// each period, and goes high once the timer counter reaches the duty
+    /// cycle.
+    Normal,
+
+    /// The duty cycle is subtracted from the period before being written
+    ///
+    /// The output is high for the duration of the duty cycle at the end of
+    /// each period.
+    Inverted,
+}
+
+impl Polarity {
+    /// Return the alignment of the pulse that the duty cycle describes
+    pub fn alignment(&self) -> Alignment {
+        match self {
+            Polarity::Normal => Alignment::Left,
+            Polarity::Inverted => Alignment::Right,
         }
     }
 }
 
+impl From<Alignment> for Polarity {
+    /// Return the polarity that results in the given alignment
+    fn from(alignment: Alignment) -> Self {
+        match alignment {
+            Alignment::Left => Polarity::Normal,
+            Alignment::Right => Polarity::Inverted,
+        }
+    }
+}
+
+/// The alignment of a PWM pulse within the period
+///
+/// As the hardware only supports edge-aligned PWM, the alignment is tied to
+/// the [`Polarity`]. It can be selected using [`Channel::set_alignment`].
+///
+/// [`Polarity`]: enum.Polarity.html
+/// [`Channel::set_alignment`]: struct.Channel.html#method.set_alignment
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Alignment {
+    /// The pulse starts at the start of the period
+    ///
+    /// The pulse is active-low. Corresponds to [`Polarity::Normal`].
+    ///
+    /// [`Polarity::Normal`]: enum.Polarity.html#variant.Normal
+    Left,
+
+    /// The pulse ends at the end of the period
+    ///
+    /// The pulse is active-high. Corresponds to [`Polarity::Inverted`].
+    ///
+    /// [`Polarity::Inverted`]: enum.Polarity.html#variant.Inverted
+    Right,
+}
+
 /// Implemented for all CTIMER PWM channels
+///
+/// The SWM function that needs to be assigned to a channel's output pin
+/// depends on the match register that defines the PWM period. Please refer to
+/// the [`period`] module for details.
+///
+/// [`period`]: ../period/index.html
 pub trait Trait: private::Sealed {
     /// Identifies the channel
     const ID: u8;
-
-    /// The SWM function that needs to be assigned to this channels output pin
-    type Output;
 }
 
 /// Contains types that indicate which state a channel is in
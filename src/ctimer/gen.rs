@@ -1,6 +1,7 @@
-use crate::swm;
-
-use super::channel::{self, Channel};
+use super::{
+    channel::{self, Channel},
+    period::MR3,
+};
 
 macro_rules! channels {
     (
@@ -8,7 +9,6 @@ macro_rules! channels {
             $channel:ident:
                 $field: ident,
                 $id:expr,
-                $output:ident,
                 $state:ident;
         )*
     ) => {
@@ -16,12 +16,12 @@ macro_rules! channels {
         ///
         /// Can be accessed via `CTIMER`.
         #[allow(missing_docs)]
-        pub struct Channels<PeripheralState, $($state,)*> {
-            $(pub $field: Channel<$channel, PeripheralState, $state>,)*
+        pub struct Channels<PeripheralState, $($state,)* Period = MR3> {
+            $(pub $field: Channel<$channel, PeripheralState, $state, Period>,)*
         }
 
-        impl<PeripheralState, $($state,)*>
-            Channels<PeripheralState, $($state,)*>
+        impl<PeripheralState, $($state,)* Period>
+            Channels<PeripheralState, $($state,)* Period>
         {
             pub(super) fn new() -> Self {
                 Self {
@@ -38,14 +38,13 @@ macro_rules! channels {
 
             impl channel::Trait for $channel {
                 const ID: u8 = $id;
-                type Output = swm::$output;
             }
         )*
     };
 }
 
 channels! {
-    Channel1: channel1, 0, T0_MAT0, State1;
-    Channel2: channel2, 1, T0_MAT1, State2;
-    Channel3: channel3, 2, T0_MAT2, State3;
+    Channel1: channel1, 0, State1;
+    Channel2: channel2, 1, State2;
+    Channel3: channel3, 2, State3;
 }
@@ -1,6 +1,12 @@
 //! API for the CTIMER peripheral
 //!
-//! Currently, only PWM output functionality is implemented.
+//! Currently, PWM output, input capture (see [`capture`]), and the use as a
+//! general-purpose timer or counter (see [`timer`]) are implemented. The match
+//! register that defines the PWM period can be selected (see [`period`]).
+//!
+//! [`capture`]: capture/index.html
+//! [`period`]: period/index.html
+//! [`timer`]: timer/index.html
 //!
 //! # Example
 //!
@@ -42,13 +48,18 @@
 //! }
 //! ```
 
+pub mod capture;
 pub mod channel;
+pub mod period;
+pub mod timer;
 
 mod gen;
 mod peripheral;
 
 pub use self::{
+    capture::Capture,
     channel::Channel,
     gen::*,
-    peripheral::{Channels1, Channels12, Channels123, CTIMER},
+    peripheral::{Channels1, Channels12, Channels123, FrequencyError, CTIMER},
+    timer::Timer,
 };
@@ -0,0 +1,100 @@
+//! Contains types that select the match register that defines the PWM period
+//!
+//! The CTIMER has four match registers. One of them resets the timer counter
+//! and thereby defines the PWM period, while the other three drive the PWM
+//! channels. By default, match register 3 defines the period, and the PWM
+//! channels 1 to 3 are output on `T0_MAT0` to `T0_MAT2`.
+//!
+//! A different match register can be selected using
+//! [`CTIMER::select_period_register`]. The match output of the selected
+//! register can't be used for PWM, and the PWM channels move to the remaining
+//! match outputs, in order. For example, if [`MR0`] defines the period, the
+//! PWM channels 1 to 3 are output on `T0_MAT1` to `T0_MAT3`.
+//!
+//! [`CTIMER::select_period_register`]: ../struct.CTIMER.html#method.select_period_register
+//! [`MR0`]: struct.MR0.html
+
+use crate::swm;
+
+/// Implemented for all match registers that can define the PWM period
+pub trait Trait: private::Sealed {
+    /// Identifies the match register
+    const ID: u8;
+
+    /// The SWM function that needs to be assigned to the output pin of PWM
+    /// channel 1
+    type Output1;
+
+    /// The SWM function that needs to be assigned to the output pin of PWM
+    /// channel 2
+    type Output2;
+
+    /// The SWM function that needs to be assigned to the output pin of PWM
+    /// channel 3
+    type Output3;
+}
+
+macro_rules! period_registers {
+    (
+        $(
+            $(#[$attr:meta])*
+            $name:ident, $id:expr, $output1:ident, $output2:ident, $output3:ident;
+        )*
+    ) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug)]
+            pub struct $name;
+
+            impl private::Sealed for $name {}
+
+            impl Trait for $name {
+                const ID: u8 = $id;
+
+                type Output1 = swm::$output1;
+                type Output2 = swm::$output2;
+                type Output3 = swm::$output3;
+            }
+        )*
+    };
+}
+
+period_registers! {
+    /// Match register 0 defines the PWM period
+    MR0, 0, T0_MAT1, T0_MAT2, T0_MAT3;
+
+    /// Match register 1 defines the PWM period
+    MR1, 1, T0_MAT0, T0_MAT2, T0_MAT3;
+
+    /// Match register 2 defines the PWM period
+    MR2, 2, T0_MAT0, T0_MAT1, T0_MAT3;
+
+    /// Match register 3 defines the PWM period
+    ///
+    /// This is the default.
+    MR3, 3, T0_MAT0, T0_MAT1, T0_MAT2;
+}
+
+/// Return the match register that drives the PWM channel with the given id
+pub(super) const fn match_register<P>(channel: u8) -> usize
+where
+    P: Trait,
+{
+    if channel >= P::ID {
+        channel as usize + 1
+    } else {
+        channel as usize
+    }
+}
+
+/// Return a bit mask of the match registers that drive PWM channels
+pub(super) const fn channel_mask<P>() -> u32
+where
+    P: Trait,
+{
+    0b1111 & !(0x1 << P::ID)
+}
+
+mod private {
+    pub trait Sealed {}
+}
@@ -6,17 +6,17 @@ use embedded_hal_alpha::pwm::blocking::{
 };
 
 use crate::{
+    clock,
     init_state::{Disabled, Enabled},
     pac::CTIMER0,
     swm, syscon,
 };
 
 use super::{
-    channel::{
-        self,
-        state::{Attached, Detached},
-    },
-    gen::{Channel1, Channel2, Channel3, Channels},
+    capture::{self, Capture, Edge},
+    channel::state::{Attached, Detached},
+    gen::Channels,
+    period::{self, MR3},
 };
 
 /// Interface to a CTimer peripheral
@@ -24,13 +24,24 @@ use super::{
 /// Controls the CTimer.  Use [`Peripherals`] to gain access to an instance of
 /// this struct.
 ///
+/// The type parameter `Period` identifies the match register that defines the
+/// PWM period. Please refer to the [`period`] module for details.
+///
 /// Please refer to the [module documentation] for more information.
 ///
 /// [`Peripherals`]: ../struct.Peripherals.html
+/// [`period`]: period/index.html
 /// [module documentation]: index.html
-pub struct CTIMER<State, Channel1State, Channel2State, Channel3State> {
+pub struct CTIMER<
+    State,
+    Channel1State,
+    Channel2State,
+    Channel3State,
+    Period = MR3,
+> {
     /// The PWM channels of this CTIMER
-    pub channels: Channels<State, Channel1State, Channel2State, Channel3State>,
+    pub channels:
+        Channels<State, Channel1State, Channel2State, Channel3State, Period>,
 
     inner: CTIMER0,
     state: State,
@@ -46,8 +57,60 @@ impl CTIMER<Disabled, Detached, Detached, Detached> {
     }
 }
 
-impl<Channel1State, Channel2State, Channel3State>
-    CTIMER<Disabled, Channel1State, Channel2State, Channel3State>
+impl<Period> CTIMER<Disabled, Detached, Detached, Detached, Period> {
+    /// Select the match register that defines the PWM period
+    ///
+    /// By default, match register 3 defines the PWM period. Selecting a
+    /// different match register moves the PWM channels to other match outputs.
+    /// Please refer to the [`period`] module for details.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{ctimer::period::MR0, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let swm = p.SWM.split();
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    ///
+    /// let pwm_output = p.pins.pio1_2.into_swm_pin();
+    ///
+    /// // Match register 0 defines the period, so channel 1 is output on
+    /// // `T0_MAT1`.
+    /// let (pwm_output, _) = swm.movable_functions.t0_mat1.assign(
+    ///     pwm_output,
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let ctimer = p
+    ///     .CTIMER0
+    ///     .select_period_register::<MR0>()
+    ///     .enable(256, 0, &mut syscon.handle)
+    ///     .attach(pwm_output);
+    /// ```
+    ///
+    /// [`period`]: period/index.html
+    pub fn select_period_register<P>(
+        self,
+    ) -> CTIMER<Disabled, Detached, Detached, Detached, P>
+    where
+        P: period::Trait,
+    {
+        CTIMER {
+            channels: Channels::new(),
+            inner: self.inner,
+            state: self.state,
+        }
+    }
+}
+
+impl<Channel1State, Channel2State, Channel3State, Period>
+    CTIMER<Disabled, Channel1State, Channel2State, Channel3State, Period>
+where
+    Period: period::Trait,
 {
     /// Start the PWM timer, with a predefined period and prescaler
     ///
@@ -58,7 +121,8 @@ impl<Channel1State, Channel2State, Channel3State>
         period: u32,
         prescaler: u32,
         syscon: &mut syscon::Handle,
-    ) -> CTIMER<Enabled, Channel1State, Channel2State, Channel3State> {
+    ) -> CTIMER<Enabled, Channel1State, Channel2State, Channel3State, Period>
+    {
         syscon.enable_clock(&self.inner);
 
         let mut self_ = CTIMER {
@@ -67,21 +131,17 @@ impl<Channel1State, Channel2State, Channel3State>
             state: Enabled(()),
         };
 
+        let channels = period::channel_mask::<Period>();
+
         unsafe { self_.inner.pr.write(|w| w.prval().bits(prescaler)) };
         self_.set_period(period);
-        self_.inner.mcr.write(|w| {
-            w.mr3r().set_bit();
-            // Use shadow registers for the pwm output matches
-            w.mr0rl().set_bit();
-            w.mr1rl().set_bit();
-            w.mr2rl().set_bit()
+        self_.inner.mcr.write(|w| unsafe {
+            // Use the period register to reset the counter, and shadow
+            // registers for the pwm output matches.
+            w.bits(MCR_RESET << (Period::ID * 3) | channels << MCR_RELOAD)
         });
 
-        self_.inner.pwmc.write(|w| {
-            w.pwmen0().set_bit();
-            w.pwmen1().set_bit();
-            w.pwmen2().set_bit()
-        });
+        self_.inner.pwmc.write(|w| unsafe { w.bits(channels) });
 
         // Start the timer
         self_.inner.tcr.write(|w| w.cen().set_bit());
@@ -90,18 +150,18 @@ impl<Channel1State, Channel2State, Channel3State>
     }
 }
 
-impl CTIMER<Enabled, Detached, Detached, Detached> {
+impl<Period> CTIMER<Enabled, Detached, Detached, Detached, Period>
+where
+    Period: period::Trait,
+{
     /// Attach an output function to channel 1
     ///
     /// This function is only available if no output functions has been attached
     /// to channel 1.
     pub fn attach<Pin>(
         self,
-        _: swm::Function<
-            <Channel1 as channel::Trait>::Output,
-            swm::state::Assigned<Pin>,
-        >,
-    ) -> CTIMER<Enabled, Attached, Detached, Detached> {
+        _: swm::Function<Period::Output1, swm::state::Assigned<Pin>>,
+    ) -> CTIMER<Enabled, Attached, Detached, Detached, Period> {
         CTIMER {
             channels: Channels::new(),
             inner: self.inner,
@@ -110,18 +170,18 @@ impl CTIMER<Enabled, Detached, Detached, Detached> {
     }
 }
 
-impl CTIMER<Enabled, Attached, Detached, Detached> {
+impl<Period> CTIMER<Enabled, Attached, Detached, Detached, Period>
+where
+    Period: period::Trait,
+{
     /// Attach an output function to channel 2
     ///
     /// This function is only available if an output function has been attached
     /// to channel 1, but no output functions has been attached to channel 2.
     pub fn attach<Pin>(
         self,
-        _: swm::Function<
-            <Channel2 as channel::Trait>::Output,
-            swm::state::Assigned<Pin>,
-        >,
-    ) -> CTIMER<Enabled, Attached, Attached, Detached> {
+        _: swm::Function<Period::Output2, swm::state::Assigned<Pin>>,
+    ) -> CTIMER<Enabled, Attached, Attached, Detached, Period> {
         CTIMER {
             channels: Channels::new(),
             inner: self.inner,
@@ -130,7 +190,10 @@ impl CTIMER<Enabled, Attached, Detached, Detached> {
     }
 }
 
-impl CTIMER<Enabled, Attached, Attached, Detached> {
+impl<Period> CTIMER<Enabled, Attached, Attached, Detached, Period>
+where
+    Period: period::Trait,
+{
     /// Attach an output function to channel 3
     ///
     /// This function is only available if output functions have been attached
@@ -138,11 +201,8 @@ impl CTIMER<Enabled, Attached, Attached, Detached> {
     /// channel 3.
     pub fn attach<Pin>(
         self,
-        _: swm::Function<
-            <Channel3 as channel::Trait>::Output,
-            swm::state::Assigned<Pin>,
-        >,
-    ) -> CTIMER<Enabled, Attached, Attached, Attached> {
+        _: swm::Function<Period::Output3, swm::state::Assigned<Pin>>,
+    ) -> CTIMER<Enabled, Attached, Attached, Attached, Period> {
         CTIMER {
             channels: Channels::new(),
             inner: self.inner,
@@ -151,8 +211,10 @@ impl CTIMER<Enabled, Attached, Attached, Detached> {
     }
 }
 
-impl<Channel1State, Channel2State, Channel3State>
-    CTIMER<Enabled, Channel1State, Channel2State, Channel3State>
+impl<Channel1State, Channel2State, Channel3State, Period>
+    CTIMER<Enabled, Channel1State, Channel2State, Channel3State, Period>
+where
+    Period: period::Trait,
 {
     /// Disable the CTIMER
     ///
@@ -168,7 +230,8 @@ impl<Channel1State, Channel2State, Channel3State>
     pub fn disable(
         self,
         syscon: &mut syscon::Handle,
-    ) -> CTIMER<Disabled, Channel1State, Channel2State, Channel3State> {
+    ) -> CTIMER<Disabled, Channel1State, Channel2State, Channel3State, Period>
+    {
         syscon.disable_clock(&self.inner);
 
         CTIMER {
@@ -178,10 +241,120 @@ impl<Channel1State, Channel2State, Channel3State>
         }
     }
 
+    /// Set the prescaler
+    ///
+    /// The timer counter is incremented every `prescaler + 1` cycles of the
+    /// main clock. This changes the PWM frequency, without affecting the
+    /// resolution of the duty cycle.
+    pub fn set_prescaler(&mut self, prescaler: u32) {
+        unsafe { self.inner.pr.write(|w| w.prval().bits(prescaler)) };
+    }
+
+    /// Return the prescaler
+    pub fn prescaler(&self) -> u32 {
+        self.inner.pr.read().prval().bits()
+    }
+
+    /// Set the PWM frequency
+    ///
+    /// Computes the period from the frequency of the clock that the CTIMER
+    /// runs on (`clock`), the current prescaler, and the requested PWM
+    /// frequency (`pwm_hz`). The resulting period becomes the maximum duty
+    /// cycle. Duty cycles are not adjusted, so you might want to set them again
+    /// afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrequencyError::TooHigh`], if the frequency can't be reached
+    /// with at least two timer ticks per period. Returns
+    /// [`FrequencyError::TooLow`], if the required period doesn't fit into the
+    /// match register. Increase the prescaler in that case.
+    ///
+    /// [`FrequencyError::TooHigh`]: enum.FrequencyError.html#variant.TooHigh
+    /// [`FrequencyError::TooLow`]: enum.FrequencyError.html#variant.TooLow
+    pub fn set_frequency<C>(
+        &mut self,
+        clock: &C,
+        pwm_hz: u32,
+    ) -> Result<(), FrequencyError>
+    where
+        C: clock::Frequency,
+    {
+        let ticks_per_period =
+            period_ticks(clock.hz(), self.prescaler(), pwm_hz)?;
+        self.set_period(ticks_per_period - 1);
+        Ok(())
+    }
+
+    /// Return the actual PWM frequency
+    ///
+    /// Computes the frequency from the frequency of the clock that the CTIMER
+    /// runs on (`clock`), the prescaler, and the period.
+    pub fn frequency<C>(&self, clock: &C) -> u32
+    where
+        C: clock::Frequency,
+    {
+        let ticks =
+            (self.prescaler() as u64 + 1) * (self.get_period() as u64 + 1);
+        (clock.hz() as u64 / ticks) as u32
+    }
+
+    /// Update the duty cycles of multiple channels synchronously
+    ///
+    /// Duty cycles are always updated at the end of a period. When changing
+    /// multiple channels, there's a chance that a period ends between the
+    /// updates, resulting in a period with a mix of old and new duty cycles.
+    ///
+    /// This method prevents that, by suspending the update of the match
+    /// registers while `f` is running. All changes made by `f` take effect at
+    /// the same period boundary.
+    pub fn update_synchronized<F>(&mut self, f: F)
+    where
+        F: FnOnce(
+            &mut Channels<
+                Enabled,
+                Channel1State,
+                Channel2State,
+                Channel3State,
+                Period,
+            >,
+        ),
+    {
+        let reload = period::channel_mask::<Period>() << MCR_RELOAD;
+
+        self.inner
+            .mcr
+            .modify(|r, w| unsafe { w.bits(r.bits() & !reload) });
+
+        f(&mut self.channels);
+
+        self.inner
+            .mcr
+            .modify(|r, w| unsafe { w.bits(r.bits() | reload) });
+    }
+
+    /// Attach an input function to a capture channel
+    ///
+    /// Returns a [`Capture`] instance that can be used to read the timer value
+    /// at the time of the selected `edge` on the input. Capture channels are
+    /// independent of the PWM channels and can be used in parallel.
+    ///
+    /// [`Capture`]: capture/struct.Capture.html
+    pub fn capture<T, Pin>(
+        &mut self,
+        _: swm::Function<T, swm::state::Assigned<Pin>>,
+        edge: Edge,
+    ) -> Capture<T, Period>
+    where
+        T: capture::Trait,
+    {
+        Capture::new(edge)
+    }
+
     // Private methods
 
     fn get_period(&self) -> u32 {
-        self.inner.mr[3].read().match_().bits()
+        self.inner.mr[Period::ID as usize].read().match_().bits()
     }
 
     fn get_max_duty(&self) -> u32 {
@@ -189,8 +362,11 @@ impl<Channel1State, Channel2State, Channel3State>
     }
 
     fn set_period(&mut self, period: u32) {
-        // Use MAT3 to reset the counter
-        unsafe { self.inner.mr[3].write(|w| w.match_().bits(period)) };
+        // Use the period register to reset the counter
+        unsafe {
+            self.inner.mr[Period::ID as usize]
+                .write(|w| w.match_().bits(period))
+        };
 
         // Reset counter. Otherwise we can run into the case where the counter
         // is already larger than period, and won't be reset until it wrapped.
@@ -199,8 +375,8 @@ impl<Channel1State, Channel2State, Channel3State>
     }
 }
 
-impl<State, Channel1State, Channel2State, Channel3State>
-    CTIMER<State, Channel1State, Channel2State, Channel3State>
+impl<State, Channel1State, Channel2State, Channel3State, Period>
+    CTIMER<State, Channel1State, Channel2State, Channel3State, Period>
 {
     /// Return the raw peripheral
     ///
@@ -219,7 +395,10 @@ impl<State, Channel1State, Channel2State, Channel3State>
     }
 }
 
-impl Pwm for CTIMER<Enabled, Attached, Detached, Detached> {
+impl<Period> Pwm for CTIMER<Enabled, Attached, Detached, Detached, Period>
+where
+    Period: period::Trait,
+{
     type Channel = Channels1;
     type Time = u32;
     type Duty = u32;
@@ -272,7 +451,10 @@ impl Pwm for CTIMER<Enabled, Attached, Detached, Detached> {
     }
 }
 
-impl Pwm for CTIMER<Enabled, Attached, Attached, Detached> {
+impl<Period> Pwm for CTIMER<Enabled, Attached, Attached, Detached, Period>
+where
+    Period: period::Trait,
+{
     type Channel = Channels12;
     type Time = u32;
     type Duty = u32;
@@ -337,7 +519,10 @@ impl Pwm for CTIMER<Enabled, Attached, Attached, Detached> {
     }
 }
 
-impl Pwm for CTIMER<Enabled, Attached, Attached, Attached> {
+impl<Period> Pwm for CTIMER<Enabled, Attached, Attached, Attached, Period>
+where
+    Period: period::Trait,
+{
     type Channel = Channels123;
     type Time = u32;
     type Duty = u32;
@@ -414,7 +599,10 @@ impl Pwm for CTIMER<Enabled, Attached, Attached, Attached> {
     }
 }
 
-impl PwmAlpha for CTIMER<Enabled, Attached, Detached, Detached> {
+impl<Period> PwmAlpha for CTIMER<Enabled, Attached, Detached, Detached, Period>
+where
+    Period: period::Trait,
+{
     type Error = Infallible;
     type Channel = Channels1;
     type Time = u32;
@@ -475,7 +663,10 @@ impl PwmAlpha for CTIMER<Enabled, Attached, Detached, Detached> {
     }
 }
 
-impl PwmAlpha for CTIMER<Enabled, Attached, Attached, Detached> {
+impl<Period> PwmAlpha for CTIMER<Enabled, Attached, Attached, Detached, Period>
+where
+    Period: period::Trait,
+{
     type Error = Infallible;
     type Channel = Channels12;
     type Time = u32;
@@ -548,7 +739,10 @@ impl PwmAlpha for CTIMER<Enabled, Attached, Attached, Detached> {
     }
 }
 
-impl PwmAlpha for CTIMER<Enabled, Attached, Attached, Attached> {
+impl<Period> PwmAlpha for CTIMER<Enabled, Attached, Attached, Attached, Period>
+where
+    Period: period::Trait,
+{
     type Error = Infallible;
     type Channel = Channels123;
     type Time = u32;
@@ -633,6 +827,45 @@ impl PwmAlpha for CTIMER<Enabled, Attached, Attached, Attached> {
     }
 }
 
+/// The reset bit of match register 0 in MCR
+const MCR_RESET: u32 = 0x1 << 1;
+
+/// Bit offset of the reload bits in MCR
+const MCR_RELOAD: u32 = 24;
+
+/// Returns the number of timer ticks per PWM period
+fn period_ticks(
+    clock_hz: u32,
+    prescaler: u32,
+    pwm_hz: u32,
+) -> Result<u32, FrequencyError> {
+    let tick_hz = clock_hz as u64 / (prescaler as u64 + 1);
+    let ticks = tick_hz / pwm_hz.max(1) as u64;
+
+    if pwm_hz == 0 || ticks > u32::MAX as u64 {
+        return Err(FrequencyError::TooLow);
+    }
+    if ticks < 2 {
+        return Err(FrequencyError::TooHigh);
+    }
+
+    Ok(ticks as u32)
+}
+
+/// Indicates that a PWM frequency can't be set
+///
+/// Returned by [`CTIMER::set_frequency`].
+///
+/// [`CTIMER::set_frequency`]: struct.CTIMER.html#method.set_frequency
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FrequencyError {
+    /// The frequency is too high for the timer clock
+    TooHigh,
+
+    /// The frequency is too low for the timer clock
+    TooLow,
+}
+
 /// The available channels, if only channel 1 is attached
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Channels1 {
@@ -0,0 +1,597 @@
+//! Contains types related to using the CTIMER as a general-purpose timer
+//!
+//! Besides PWM generation, the CTIMER can be used as a 32-bit timer that counts
+//! the cycles of the main clock (divided by a prescaler), or as a counter that
+//! counts the edges of an external signal on one of the capture inputs. In
+//! both cases, the CTIMER is represented by [`Timer`].
+//!
+//! Match register 3 is used to implement the [`CountDown`] and [`Periodic`]
+//! traits. Match registers 0 to 2 can be configured freely, using
+//! [`Timer::set_match`].
+//!
+//! A timer can also be started in free-running mode, using
+//! [`Timer::start_free_running`]. In that mode, the timer counter is never
+//! reset and wraps around at `u32::MAX`, and [`embedded_time::Clock`] is
+//! implemented. The frequency of the system clock is part of the type, and
+//! checked against the actual clock configuration when the timer is started.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use lpc8xx_hal::{
+//!     ctimer::timer::{MatchAction, MatchChannel},
+//!     prelude::*,
+//!     Peripherals,
+//! };
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//!
+//! // Count in microseconds, assuming the main clock runs at 12 MHz
+//! let mut timer = p.CTIMER0.enable_timer(11, &mut syscon.handle);
+//!
+//! // Set a flag 250 µs into each period
+//! timer.set_match(
+//!     MatchChannel::Match0,
+//!     250,
+//!     MatchAction {
+//!         interrupt: true,
+//!         ..MatchAction::default()
+//!     },
+//! );
+//!
+//! timer.start(1000_u32);
+//! loop {
+//!     if timer.is_matched(MatchChannel::Match0) {
+//!         timer.clear_match(MatchChannel::Match0);
+//!         // do something
+//!     }
+//!     if timer.wait().is_ok() {
+//!         // a full period has passed
+//!     }
+//! }
+//! ```
+//!
+//! [`Timer`]: struct.Timer.html
+//! [`CountDown`]: embedded_hal::timer::CountDown
+//! [`Periodic`]: embedded_hal::timer::Periodic
+//! [`Timer::set_match`]: struct.Timer.html#method.set_match
+//! [`Timer::start_free_running`]: struct.Timer.html#method.start_free_running
+//! [`embedded_time::Clock`]: embedded_time::Clock
+
+use core::{fmt, marker::PhantomData};
+
+use embedded_hal::timer::{CountDown, Periodic};
+use embedded_hal_alpha::timer::{
+    nb::CountDown as CountDownAlpha, Periodic as PeriodicAlpha,
+};
+use embedded_time::{clock, fraction::Fraction, Instant};
+use void::Void;
+
+use crate::{
+    clock::Frequency as _, init_state::Disabled, pac::CTIMER0, swm, syscon,
+};
+
+use super::{
+    capture::{self, Capture, Edge},
+    channel::state::Detached,
+    CTIMER,
+};
+
+/// The CTIMER, used as a general-purpose timer or counter
+///
+/// Can be created using [`CTIMER::enable_timer`] or
+/// [`CTIMER::enable_counter`]. The `Mode` type parameter indicates what the
+/// timer counts (see [`mode`]).
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::timer::CountDown`]
+/// - [`embedded_hal::timer::Periodic`]
+///
+/// # `embedded-time` traits
+/// - [`embedded_time::Clock`] (only in [`mode::FreeRunning`])
+///
+/// [`CTIMER::enable_timer`]: ../struct.CTIMER.html#method.enable_timer
+/// [`CTIMER::enable_counter`]: ../struct.CTIMER.html#method.enable_counter
+/// [`mode`]: mode/index.html
+/// [`mode::FreeRunning`]: mode/struct.FreeRunning.html
+/// [`embedded_hal::timer::CountDown`]: #impl-CountDown
+/// [`embedded_hal::timer::Periodic`]: #impl-Periodic
+/// [`embedded_time::Clock`]: #impl-Clock
+pub struct Timer<Mode> {
+    inner: CTIMER0,
+    _mode: PhantomData<Mode>,
+}
+
+impl<Period> CTIMER<Disabled, Detached, Detached, Detached, Period> {
+    /// Enable the CTIMER as a general-purpose timer
+    ///
+    /// The timer counter is incremented every `prescaler + 1` cycles of the
+    /// main clock. The timer is not running until [`Timer::start`] is called.
+    ///
+    /// [`Timer::start`]: timer/struct.Timer.html#method.start
+    pub fn enable_timer(
+        self,
+        prescaler: u32,
+        syscon: &mut syscon::Handle,
+    ) -> Timer<mode::Timer> {
+        let timer = Timer::new(self.free(), syscon);
+
+        timer.inner.ctcr.write(|w| w.ctmode().timer());
+        unsafe { timer.inner.pr.write(|w| w.prval().bits(prescaler)) };
+
+        timer
+    }
+
+    /// Enable the CTIMER as a counter of external pulses
+    ///
+    /// The timer counter is incremented on every `edge` of the signal on the
+    /// capture input that `input` is assigned to. The prescaler is not used.
+    /// The counter is not running until [`Timer::start`] is called.
+    ///
+    /// The capture channel that belongs to `input` can not be used for input
+    /// capture at the same time.
+    ///
+    /// [`Timer::start`]: timer/struct.Timer.html#method.start
+    pub fn enable_counter<T, Pin>(
+        self,
+        _: swm::Function<T, swm::state::Assigned<Pin>>,
+        edge: Edge,
+        syscon: &mut syscon::Handle,
+    ) -> Timer<mode::Counter>
+    where
+        T: capture::Trait,
+    {
+        let timer = Timer::new(self.free(), syscon);
+
+        timer.inner.ctcr.write(|w| {
+            match edge {
+                Edge::Rising => w.ctmode().counter_rising_edge(),
+                Edge::Falling => w.ctmode().counter_falling_edge(),
+                Edge::Both => w.ctmode().counter_dual_edge(),
+            };
+            w.cinsel().bits(T::ID)
+        });
+        unsafe { timer.inner.pr.write(|w| w.prval().bits(0)) };
+
+        timer
+    }
+}
+
+impl<Mode> Timer<Mode> {
+    fn new(inner: CTIMER0, syscon: &mut syscon::Handle) -> Self {
+        syscon.enable_clock(&inner);
+
+        // Hold the counter in reset until the timer is started.
+        inner.tcr.write(|w| w.crst().enabled());
+        inner.mcr.reset();
+        inner.pwmc.reset();
+        inner.emr.reset();
+        inner.ir.write(|w| unsafe { w.bits(0xff) });
+
+        Self {
+            inner,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Stop the timer
+    ///
+    /// The counter keeps its value, until the timer is started again.
+    pub fn stop(&mut self) {
+        self.inner.tcr.modify(|_, w| w.cen().disabled());
+    }
+
+    /// Indicates whether the timer is running
+    pub fn is_running(&self) -> bool {
+        self.inner.tcr.read().cen().is_enabled()
+    }
+
+    /// Returns the prescaler
+    ///
+    /// The timer counter is incremented every `prescaler + 1` cycles of the
+    /// main clock. Always zero in counter mode.
+    pub fn prescaler(&self) -> u32 {
+        self.inner.pr.read().prval().bits()
+    }
+
+    /// Returns the current value of the timer counter
+    pub fn value(&self) -> u32 {
+        self.inner.tc.read().tcval().bits()
+    }
+
+    /// Configure a match register
+    ///
+    /// Whenever the timer counter reaches `value`, the actions selected in
+    /// `action` are taken. Please note that resetting or stopping the timer
+    /// from a match register interferes with the [`CountDown`] implementation,
+    /// and, in free-running mode, with the [`embedded_time::Clock`]
+    /// implementation.
+    ///
+    /// [`CountDown`]: #impl-CountDown
+    /// [`embedded_time::Clock`]: #impl-Clock
+    pub fn set_match(
+        &mut self,
+        channel: MatchChannel,
+        value: u32,
+        action: MatchAction,
+    ) {
+        let id = channel as usize;
+
+        unsafe { self.inner.mr[id].write(|w| w.match_().bits(value)) };
+
+        let bits = action.interrupt as u32
+            | (action.reset as u32) << 1
+            | (action.stop as u32) << 2;
+        let shift = id * 3;
+        self.inner.mcr.modify(|r, w| unsafe {
+            w.bits((r.bits() & !(0b111 << shift)) | bits << shift)
+        });
+    }
+
+    /// Indicates whether the timer counter has reached the value of the match
+    /// register
+    ///
+    /// The flag is only set, if [`MatchAction::interrupt`] has been enabled for
+    /// the match register. It stays set until it is cleared using
+    /// [`Timer::clear_match`].
+    ///
+    /// [`MatchAction::interrupt`]: struct.MatchAction.html#structfield.interrupt
+    /// [`Timer::clear_match`]: #method.clear_match
+    pub fn is_matched(&self, channel: MatchChannel) -> bool {
+        self.inner.ir.read().bits() & 0x1 << channel as u32 != 0
+    }
+
+    /// Clear the flag (and interrupt) of a match register
+    pub fn clear_match(&mut self, channel: MatchChannel) {
+        self.inner
+            .ir
+            .write(|w| unsafe { w.bits(0x1 << channel as u32) });
+    }
+
+    /// Attach an input function to a capture channel
+    ///
+    /// Works like [`CTIMER::capture`]. Captured values are values of the timer
+    /// counter, which counts ticks or external pulses, depending on the mode.
+    ///
+    /// [`CTIMER::capture`]: ../struct.CTIMER.html#method.capture
+    pub fn capture<T, Pin>(
+        &mut self,
+        _: swm::Function<T, swm::state::Assigned<Pin>>,
+        edge: Edge,
+    ) -> Capture<T>
+    where
+        T: capture::Trait,
+    {
+        Capture::new(edge)
+    }
+
+    /// Disable the timer
+    ///
+    /// Returns the disabled CTIMER, which can then be enabled in any mode
+    /// again.
+    pub fn disable(
+        self,
+        syscon: &mut syscon::Handle,
+    ) -> CTIMER<Disabled, Detached, Detached, Detached> {
+        self.inner.tcr.reset();
+        self.inner.mcr.reset();
+        self.inner.ccr.reset();
+        self.inner.ctcr.reset();
+
+        syscon.disable_clock(&self.inner);
+
+        CTIMER::new(self.inner)
+    }
+
+    /// Return the raw peripheral
+    ///
+    /// This method serves as an escape hatch from the HAL API. It returns the
+    /// raw peripheral, allowing you to do whatever you want with it, without
+    /// limitations imposed by the API.
+    ///
+    /// If you are using this method because a feature you need is missing from
+    /// the HAL API, please [open an issue] or, if an issue for your feature
+    /// request already exists, comment on the existing issue, so we can
+    /// prioritize it accordingly.
+    ///
+    /// [open an issue]: https://github.com/lpc-rs/lpc8xx-hal/issues
+    pub fn free(self) -> CTIMER0 {
+        self.inner
+    }
+}
+
+impl<Mode> Timer<Mode>
+where
+    Mode: mode::Periodic,
+{
+    /// Start the timer
+    ///
+    /// The timer counts up from zero. When it has counted `ticks` ticks, the
+    /// match flag for match register 3 is set, and the counter is reset to
+    /// zero, causing the timer to run periodically.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `ticks` is zero.
+    pub fn start(&mut self, ticks: u32) {
+        assert!(ticks > 0);
+
+        self.inner.tcr.write(|w| w.crst().enabled());
+
+        unsafe { self.inner.mr[3].write(|w| w.match_().bits(ticks - 1)) };
+        self.inner.mcr.modify(|_, w| {
+            w.mr3i().set_bit();
+            w.mr3r().set_bit();
+            w.mr3s().clear_bit()
+        });
+        self.inner.ir.write(|w| w.mr3int().set_bit());
+
+        self.inner.tcr.write(|w| {
+            w.crst().disabled();
+            w.cen().enabled()
+        });
+    }
+
+    /// Non-blockingly "waits" until the count down finishes
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        if self.inner.ir.read().mr3int().bit_is_set() {
+            self.inner.ir.write(|w| w.mr3int().set_bit());
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+impl Timer<mode::Timer> {
+    /// Start the timer in free-running mode
+    ///
+    /// The timer counts up from zero, incrementing the timer counter every
+    /// `PRESCALER + 1` cycles of the system clock, overriding the prescaler
+    /// that was passed to [`CTIMER::enable_timer`]. Unlike with
+    /// [`Timer::start`], the timer counter is never reset, but wraps around at
+    /// `u32::MAX`.
+    ///
+    /// `CLOCK_HZ` is the frequency of the system clock, which determines the
+    /// scaling factor of the [`embedded_time::Clock`] implementation.
+    ///
+    /// Consumes this timer and returns one in [`mode::FreeRunning`], which
+    /// implements [`embedded_time::Clock`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `CLOCK_HZ` doesn't match the frequency of `clock`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{embedded_time::Clock as _, prelude::*, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// let mut syscon = p.SYSCON.split();
+    ///
+    /// // Count in microseconds. The system clock runs at 12 MHz by default.
+    /// let clock = p
+    ///     .CTIMER0
+    ///     .enable_timer(0, &mut syscon.handle)
+    ///     .start_free_running::<12_000_000, 11>(&syscon.system_clock);
+    ///
+    /// let start = clock.try_now().unwrap();
+    /// // do something
+    /// let elapsed = clock.try_now().unwrap() - start;
+    /// ```
+    ///
+    /// [`CTIMER::enable_timer`]: ../struct.CTIMER.html#method.enable_timer
+    /// [`Timer::start`]: #method.start
+    /// [`mode::FreeRunning`]: mode/struct.FreeRunning.html
+    /// [`embedded_time::Clock`]: #impl-Clock
+    pub fn start_free_running<const CLOCK_HZ: u32, const PRESCALER: u32>(
+        self,
+        clock: &syscon::SystemClock,
+    ) -> Timer<mode::FreeRunning<CLOCK_HZ, PRESCALER>> {
+        assert_eq!(
+            clock.hz(),
+            CLOCK_HZ,
+            "System clock frequency doesn't match `CLOCK_HZ`",
+        );
+
+        self.inner.tcr.write(|w| w.crst().enabled());
+
+        unsafe { self.inner.pr.write(|w| w.prval().bits(PRESCALER)) };
+        self.inner.mcr.modify(|_, w| {
+            w.mr3i().clear_bit();
+            w.mr3r().clear_bit();
+            w.mr3s().clear_bit()
+        });
+        self.inner.ir.write(|w| w.mr3int().set_bit());
+
+        self.inner.tcr.write(|w| {
+            w.crst().disabled();
+            w.cen().enabled()
+        });
+
+        Timer {
+            inner: self.inner,
+            _mode: PhantomData,
+        }
+    }
+}
+
+impl<const CLOCK_HZ: u32, const PRESCALER: u32>
+    Timer<mode::FreeRunning<CLOCK_HZ, PRESCALER>>
+{
+    /// Stop the free-running timer
+    ///
+    /// Returns a timer in [`mode::Timer`], which can be started again using
+    /// [`Timer::start`] or [`Timer::start_free_running`]. The prescaler stays
+    /// at `PRESCALER`.
+    ///
+    /// [`mode::Timer`]: mode/struct.Timer.html
+    /// [`Timer::start`]: #method.start
+    /// [`Timer::start_free_running`]: #method.start_free_running
+    pub fn stop_free_running(self) -> Timer<mode::Timer> {
+        self.inner.tcr.write(|w| w.crst().enabled());
+
+        Timer {
+            inner: self.inner,
+            _mode: PhantomData,
+        }
+    }
+}
+
+impl<Mode> CountDown for Timer<Mode>
+where
+    Mode: mode::Periodic,
+{
+    /// Ticks of the timer counter
+    ///
+    /// In timer mode, these are cycles of the main clock, divided by the
+    /// prescaler. In counter mode, these are edges of the external signal.
+    type Time = u32;
+
+    fn start<Time>(&mut self, count: Time)
+    where
+        Time: Into<Self::Time>,
+    {
+        self.start(count.into());
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        self.wait()
+    }
+}
+
+impl<Mode> CountDownAlpha for Timer<Mode>
+where
+    Mode: mode::Periodic,
+{
+    type Error = Void;
+
+    /// Ticks of the timer counter
+    ///
+    /// In timer mode, these are cycles of the main clock, divided by the
+    /// prescaler. In counter mode, these are edges of the external signal.
+    type Time = u32;
+
+    fn start<Time>(&mut self, count: Time) -> Result<(), Self::Error>
+    where
+        Time: Into<Self::Time>,
+    {
+        self.start(count.into());
+        Ok(())
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Self::Error> {
+        self.wait()
+    }
+}
+
+impl<Mode> Periodic for Timer<Mode> where Mode: mode::Periodic {}
+
+impl<Mode> PeriodicAlpha for Timer<Mode> where Mode: mode::Periodic {}
+
+impl<const CLOCK_HZ: u32, const PRESCALER: u32> embedded_time::Clock
+    for Timer<mode::FreeRunning<CLOCK_HZ, PRESCALER>>
+{
+    type T = u32;
+
+    const SCALING_FACTOR: Fraction = Fraction::new(PRESCALER + 1, CLOCK_HZ);
+
+    fn try_now(&self) -> Result<Instant<Self>, clock::Error> {
+        if !self.is_running() {
+            return Err(clock::Error::NotRunning);
+        }
+
+        Ok(Instant::new(self.value()))
+    }
+}
+
+// Can't derive, because peripheral structs from the PAC don't implement
+// `Debug`. See https://github.com/rust-embedded/svd2rust/issues/48.
+impl<Mode> fmt::Debug for Timer<Mode> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timer")
+            .field("inner", &"CTIMER0")
+            .field("_mode", &self._mode)
+            .finish()
+    }
+}
+
+/// Identifies a match register that can be configured freely
+///
+/// Match register 3 is used by the [`CountDown`] implementation of [`Timer`].
+///
+/// [`CountDown`]: struct.Timer.html#impl-CountDown
+/// [`Timer`]: struct.Timer.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MatchChannel {
+    /// Match register 0
+    Match0 = 0,
+
+    /// Match register 1
+    Match1 = 1,
+
+    /// Match register 2
+    Match2 = 2,
+}
+
+/// Actions that are taken when the timer counter reaches a match value
+///
+/// Used with [`Timer::set_match`]. The default is to take no action.
+///
+/// [`Timer::set_match`]: struct.Timer.html#method.set_match
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MatchAction {
+    /// Set the match flag and request an interrupt
+    pub interrupt: bool,
+
+    /// Reset the timer counter
+    pub reset: bool,
+
+    /// Stop the timer
+    pub stop: bool,
+}
+
+/// Contains types that indicate the mode of a [`Timer`]
+///
+/// [`Timer`]: ../struct.Timer.html
+pub mod mode {
+    /// The timer counts cycles of the main clock
+    #[derive(Debug)]
+    pub struct Timer;
+
+    /// The timer counts edges of an external signal
+    #[derive(Debug)]
+    pub struct Counter;
+
+    /// The timer counts cycles of the system clock and is never reset
+    ///
+    /// The timer counter is incremented every `PRESCALER + 1` cycles of the
+    /// system clock, which runs at `CLOCK_HZ`. See
+    /// [`Timer::start_free_running`].
+    ///
+    /// [`Timer::start_free_running`]: ../struct.Timer.html#method.start_free_running
+    #[derive(Debug)]
+    pub struct FreeRunning<const CLOCK_HZ: u32, const PRESCALER: u32>;
+
+    /// Implemented for modes, in which the timer runs periodically
+    ///
+    /// In these modes, the timer implements [`CountDown`] and [`Periodic`].
+    ///
+    /// [`CountDown`]: ../struct.Timer.html#impl-CountDown
+    /// [`Periodic`]: ../struct.Timer.html#impl-Periodic
+    pub trait Periodic: private::Sealed {}
+
+    impl Periodic for Timer {}
+    impl Periodic for Counter {}
+
+    mod private {
+        pub trait Sealed {}
+
+        impl Sealed for super::Timer {}
+        impl Sealed for super::Counter {}
+    }
+}
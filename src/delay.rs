@@ -1,7 +1,12 @@
-//! API for delays with the systick timer
+//! API for delays
 //!
-//! Please be aware of potential overflows when using `delay_us`.
-//! E.g. at 30MHz the maximum delay is 146 seconds.
+//! [`Delay`] uses the system timer (SysTick). Please be aware of potential
+//! overflows when using `delay_us`. E.g. at 30MHz the maximum delay is 146
+//! seconds.
+//!
+//! If SysTick is needed for something else, like an RTOS kernel, [`MrtDelay`]
+//! or [`CtimerDelay`] (LPC845 only) can be used instead. Those take the actual
+//! frequency of the system clock into account, and don't overflow.
 //!
 //! # Example
 //!
@@ -19,14 +24,29 @@
 //!     delay.delay_ms(1_000_u16);
 //! }
 //! ```
+//!
+//! [`Delay`]: struct.Delay.html
+//! [`MrtDelay`]: struct.MrtDelay.html
+//! [`CtimerDelay`]: struct.CtimerDelay.html
 
 use cortex_m::peripheral::syst::SystClkSource;
 
-use crate::pac::SYST;
-use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+use crate::{
+    clock::Frequency as _,
+    mrt::{self, mode},
+    pac::SYST,
+    syscon,
+};
+use embedded_hal::{
+    blocking::delay::{DelayMs, DelayUs},
+    timer::CountDown,
+};
 use embedded_hal_alpha::delay::blocking::DelayUs as DelayUsAlpha;
 use void::Void;
 
+#[cfg(feature = "845")]
+use crate::ctimer;
+
 const SYSTICK_RANGE: u32 = 0x0100_0000;
 const SYSTEM_CLOCK: u32 = 12_000_000;
 
@@ -142,3 +162,308 @@ impl DelayUs<u8> for Delay {
         DelayUs::delay_us(self, us as u32)
     }
 }
+
+/// MRT channel as a delay provider
+///
+/// The `Mode` type parameter indicates whether the channel runs in one-shot
+/// mode, in which case the delay is implemented by polling the channel, or in
+/// one-shot bus-stall mode. In bus-stall mode, the bus is stalled for the
+/// duration of the delay, which is more precise, but also blocks interrupts
+/// and DMA transfers.
+///
+/// # Example
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     delay::MrtDelay,
+///     Peripherals,
+/// };
+///
+/// let p = Peripherals::take().unwrap();
+///
+/// let mut syscon = p.SYSCON.split();
+/// let mrt = p.MRT0.split(&mut syscon.handle);
+///
+/// let mut delay = MrtDelay::new(mrt.mrt0, &syscon.system_clock);
+/// loop {
+///     delay.delay_ms(1_000_u16);
+/// }
+/// ```
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::blocking::delay::DelayUs`]
+/// - [`embedded_hal::blocking::delay::DelayMs`]
+///
+/// [`embedded_hal::blocking::delay::DelayUs`]: #impl-DelayUs%3Cu32%3E
+/// [`embedded_hal::blocking::delay::DelayMs`]: #impl-DelayMs%3Cu32%3E
+pub struct MrtDelay<T: mrt::Trait, Mode = mode::OneShot> {
+    channel: mrt::Channel<T, Mode>,
+    clock_hz: u32,
+}
+
+impl<T> MrtDelay<T>
+where
+    T: mrt::Trait,
+{
+    /// Use an MRT channel as a delay provider, in one-shot mode
+    ///
+    /// The frequency of the system clock that the MRT runs on is read from
+    /// `clock` once, so the delay needs to be created again, if the system
+    /// clock configuration changes.
+    pub fn new<Mode>(
+        channel: mrt::Channel<T, Mode>,
+        clock: &syscon::SystemClock,
+    ) -> Self {
+        Self {
+            channel: channel.into_one_shot(),
+            clock_hz: clock.hz(),
+        }
+    }
+}
+
+impl<T> MrtDelay<T, mode::OneShotStall>
+where
+    T: mrt::Trait,
+{
+    /// Use an MRT channel as a delay provider, in one-shot bus-stall mode
+    ///
+    /// The frequency of the system clock that the MRT runs on is read from
+    /// `clock` once, so the delay needs to be created again, if the system
+    /// clock configuration changes.
+    pub fn new_stalling<Mode>(
+        channel: mrt::Channel<T, Mode>,
+        clock: &syscon::SystemClock,
+    ) -> Self {
+        Self {
+            channel: channel.into_one_shot_stall(),
+            clock_hz: clock.hz(),
+        }
+    }
+}
+
+impl<T, Mode> MrtDelay<T, Mode>
+where
+    T: mrt::Trait,
+{
+    /// Return the MRT channel
+    pub fn free(self) -> mrt::Channel<T, Mode> {
+        self.channel
+    }
+}
+
+impl<T> TickDelay for MrtDelay<T>
+where
+    T: mrt::Trait,
+{
+    fn clock_hz(&self) -> u32 {
+        self.clock_hz
+    }
+
+    fn delay_ticks(&mut self, ticks: u64) {
+        for_each_chunk(ticks, mrt::MAX_VALUE.to_u32(), |ticks| {
+            // Sound, as `for_each_chunk` never passes more than `MAX_VALUE`.
+            self.channel.start(unsafe { mrt::Ticks::from_u32(ticks) });
+            while CountDown::wait(&mut self.channel).is_err() {}
+        });
+    }
+}
+
+impl<T> TickDelay for MrtDelay<T, mode::OneShotStall>
+where
+    T: mrt::Trait,
+{
+    fn clock_hz(&self) -> u32 {
+        self.clock_hz
+    }
+
+    fn delay_ticks(&mut self, ticks: u64) {
+        for_each_chunk(ticks, mrt::MAX_VALUE.to_u32(), |ticks| {
+            // Sound, as `for_each_chunk` never passes more than `MAX_VALUE`.
+            self.channel.delay(unsafe { mrt::Ticks::from_u32(ticks) });
+        });
+    }
+}
+
+/// CTIMER as a delay provider
+///
+/// # `embedded-hal` traits
+/// - [`embedded_hal::blocking::delay::DelayUs`]
+/// - [`embedded_hal::blocking::delay::DelayMs`]
+///
+/// [`embedded_hal::blocking::delay::DelayUs`]: #impl-DelayUs%3Cu32%3E
+/// [`embedded_hal::blocking::delay::DelayMs`]: #impl-DelayMs%3Cu32%3E
+#[cfg(feature = "845")]
+pub struct CtimerDelay {
+    timer: ctimer::Timer<ctimer::timer::mode::Timer>,
+    clock_hz: u32,
+}
+
+#[cfg(feature = "845")]
+impl CtimerDelay {
+    /// Use the CTIMER as a delay provider
+    ///
+    /// The frequency of the system clock that the CTIMER runs on is read from
+    /// `clock` once, so the delay needs to be created again, if the system
+    /// clock configuration changes. The prescaler that the timer was enabled
+    /// with is taken into account.
+    pub fn new(
+        timer: ctimer::Timer<ctimer::timer::mode::Timer>,
+        clock: &syscon::SystemClock,
+    ) -> Self {
+        let clock_hz = clock.hz() / (timer.prescaler() + 1);
+        Self { timer, clock_hz }
+    }
+
+    /// Return the timer
+    pub fn free(self) -> ctimer::Timer<ctimer::timer::mode::Timer> {
+        self.timer
+    }
+}
+
+#[cfg(feature = "845")]
+impl TickDelay for CtimerDelay {
+    fn clock_hz(&self) -> u32 {
+        self.clock_hz
+    }
+
+    fn delay_ticks(&mut self, ticks: u64) {
+        for_each_chunk(ticks, u32::MAX, |ticks| {
+            self.timer.start(ticks);
+            while CountDown::wait(&mut self.timer).is_err() {}
+        });
+        self.timer.stop();
+    }
+}
+
+/// Implemented by delay providers that are based on a timer
+trait TickDelay {
+    /// The frequency of the timer in Hz
+    fn clock_hz(&self) -> u32;
+
+    /// Pauses execution for `ticks` timer ticks
+    fn delay_ticks(&mut self, ticks: u64);
+
+    /// Pauses execution for `us` microseconds
+    fn delay_micros(&mut self, us: u64) {
+        self.delay_ticks(micros_to_ticks(us, self.clock_hz()));
+    }
+}
+
+/// Return the number of timer ticks that make up `us` microseconds
+///
+/// `clock_hz` is the frequency of the timer. The result is rounded up, so a
+/// delay of that many ticks never waits less than requested. The computation
+/// can't overflow for any delay that the `DelayMs` and `DelayUs`
+/// implementations in this module accept.
+///
+/// ```
+/// use lpc8xx_hal::delay::micros_to_ticks;
+///
+/// assert_eq!(micros_to_ticks(1, 12_000_000), 12);
+/// assert_eq!(micros_to_ticks(1, 750_000), 1);
+///
+/// // The longest possible delay, `delay_ms(u32::MAX)`, at 30 MHz
+/// assert_eq!(
+///     micros_to_ticks(u32::MAX as u64 * 1_000, 30_000_000),
+///     u32::MAX as u64 * 30_000,
+/// );
+/// ```
+pub fn micros_to_ticks(us: u64, clock_hz: u32) -> u64 {
+    // Round up, to never wait less than requested.
+    let ticks = (us as u128 * clock_hz as u128).div_ceil(1_000_000);
+    ticks as u64
+}
+
+/// Calls `f` with chunks of `ticks` that are at most `max` and not zero
+fn for_each_chunk(mut ticks: u64, max: u32, mut f: impl FnMut(u32)) {
+    while ticks > 0 {
+        let chunk = ticks.min(max as u64);
+        f(chunk as u32);
+        ticks -= chunk;
+    }
+}
+
+macro_rules! tick_delay {
+    ($(
+        impl[$($generics:tt)*] $ty:ty $(where [$($bounds:tt)*])?;
+    )*) => {
+        $(
+            impl<$($generics)*> DelayMs<u32> for $ty
+            $(where $($bounds)*)?
+            {
+                /// Pauses execution for `ms` milliseconds
+                fn delay_ms(&mut self, ms: u32) {
+                    self.delay_micros(ms as u64 * 1_000);
+                }
+            }
+
+            impl<$($generics)*> DelayMs<u16> for $ty
+            $(where $($bounds)*)?
+            {
+                /// Pauses execution for `ms` milliseconds
+                fn delay_ms(&mut self, ms: u16) {
+                    self.delay_micros(ms as u64 * 1_000);
+                }
+            }
+
+            impl<$($generics)*> DelayMs<u8> for $ty
+            $(where $($bounds)*)?
+            {
+                /// Pauses execution for `ms` milliseconds
+                fn delay_ms(&mut self, ms: u8) {
+                    self.delay_micros(ms as u64 * 1_000);
+                }
+            }
+
+            impl<$($generics)*> DelayUs<u32> for $ty
+            $(where $($bounds)*)?
+            {
+                /// Pauses execution for `us` microseconds
+                fn delay_us(&mut self, us: u32) {
+                    self.delay_micros(us as u64);
+                }
+            }
+
+            impl<$($generics)*> DelayUs<u16> for $ty
+            $(where $($bounds)*)?
+            {
+                /// Pauses execution for `us` microseconds
+                fn delay_us(&mut self, us: u16) {
+                    self.delay_micros(us as u64);
+                }
+            }
+
+            impl<$($generics)*> DelayUs<u8> for $ty
+            $(where $($bounds)*)?
+            {
+                /// Pauses execution for `us` microseconds
+                fn delay_us(&mut self, us: u8) {
+                    self.delay_micros(us as u64);
+                }
+            }
+
+            impl<$($generics)*> DelayUsAlpha for $ty
+            $(where $($bounds)*)?
+            {
+                type Error = Void;
+
+                /// Pauses execution for `us` microseconds
+                fn delay_us(&mut self, us: u32) -> Result<(), Self::Error> {
+                    self.delay_micros(us as u64);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+tick_delay! {
+    impl[T] MrtDelay<T> where [T: mrt::Trait];
+    impl[T] MrtDelay<T, mode::OneShotStall> where [T: mrt::Trait];
+}
+
+#[cfg(feature = "845")]
+tick_delay! {
+    impl[] CtimerDelay;
+}
@@ -3,12 +3,16 @@ use crate::{
     void::Void,
 };
 
-use super::{Dest, Source};
+use super::{Dest, Source, Word};
 
-impl crate::private::Sealed for &'static [u8] {}
+impl<W> crate::private::Sealed for &'static [W] where W: Word {}
 
-impl Source for &'static [u8] {
+impl<W> Source for &'static [W]
+where
+    W: Word,
+{
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         self.len() <= 1024
@@ -34,7 +38,7 @@ impl Source for &'static [u8] {
 
     fn end_addr(&self) -> *const u8 {
         // Sound, as we stay within the bounds of the slice.
-        unsafe { self.as_ptr().add(self.len() - 1) }
+        unsafe { self.as_ptr().add(self.len() - 1) as *const u8 }
     }
 
     fn finish(&mut self) -> nb::Result<(), Self::Error> {
@@ -42,11 +46,15 @@ impl Source for &'static [u8] {
     }
 }
 
-impl crate::private::Sealed for &'static mut [u8] {}
+impl<W> crate::private::Sealed for &'static mut [W] where W: Word {}
 
-impl Dest for &'static mut [u8] {
+impl<W> Dest for &'static mut [W]
+where
+    W: Word,
+{
     /// The error that can occur while waiting for the destination to be idle
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         self.len() <= 1024
@@ -72,7 +80,7 @@ impl Dest for &'static mut [u8] {
 
     fn end_addr(&mut self) -> *mut u8 {
         // Sound, as we stay within the bounds of the slice.
-        unsafe { self.as_mut_ptr().add(self.len() - 1) }
+        unsafe { self.as_mut_ptr().add(self.len() - 1) as *mut u8 }
     }
 
     fn finish(&mut self) -> nb::Result<(), Self::Error> {
@@ -80,12 +88,15 @@ impl Dest for &'static mut [u8] {
     }
 }
 
-pub(crate) struct Buffer {
-    ptr: *mut u8,
+pub(crate) struct Buffer<W = u8> {
+    ptr: *mut W,
     len: usize,
 }
 
-impl Buffer {
+impl<W> Buffer<W>
+where
+    W: Word,
+{
     /// Create a `Buffer` from a static slice
     ///
     /// # Unsafety
@@ -100,15 +111,19 @@ impl Buffer {
     /// - If the `Buffer` instance is used as a DMA destination, it is the
     ///   caller's responsibility to only pass a reference to a mutable slice,
     ///   even though this method accepts references to immutable slices.
-    pub(crate) unsafe fn new(ptr: *mut u8, len: usize) -> Self {
+    pub(crate) unsafe fn new(ptr: *mut W, len: usize) -> Self {
         Self { ptr, len }
     }
 }
 
-impl crate::private::Sealed for Buffer {}
+impl<W> crate::private::Sealed for Buffer<W> {}
 
-impl Source for Buffer {
+impl<W> Source for Buffer<W>
+where
+    W: Word,
+{
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         self.len <= 1024
@@ -134,7 +149,7 @@ impl Source for Buffer {
 
     fn end_addr(&self) -> *const u8 {
         // Sound, as we stay within the bounds of the slice.
-        unsafe { self.ptr.add(self.len - 1) }
+        unsafe { self.ptr.add(self.len - 1) as *const u8 }
     }
 
     fn finish(&mut self) -> nb::Result<(), Self::Error> {
@@ -142,9 +157,13 @@ impl Source for Buffer {
     }
 }
 
-impl Dest for Buffer {
+impl<W> Dest for Buffer<W>
+where
+    W: Word,
+{
     /// The error that can occur while waiting for the destination to be idle
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         self.len <= 1024
@@ -170,7 +189,7 @@ impl Dest for Buffer {
 
     fn end_addr(&mut self) -> *mut u8 {
         // Sound, as we stay within the bounds of the slice.
-        unsafe { self.ptr.add(self.len - 1) }
+        unsafe { self.ptr.add(self.len - 1) as *mut u8 }
     }
 
     fn finish(&mut self) -> nb::Result<(), Self::Error> {
@@ -8,8 +8,8 @@ use crate::{
         self,
         dma0::{
             channel::{CFG, XFERCFG},
-            ACTIVE0, BUSY0, ENABLESET0, ERRINT0, INTA0, INTB0, INTENCLR0,
-            INTENSET0, SETTRIG0,
+            ABORT0, ACTIVE0, BUSY0, ENABLECLR0, ENABLESET0, ERRINT0, INTA0,
+            INTB0, INTENCLR0, INTENSET0, SETTRIG0,
         },
     },
     reg_proxy::{Reg, RegProxy},
@@ -113,8 +113,10 @@ pub trait Instance {
 }
 
 pub(super) struct SharedRegisters<C> {
+    abort0: &'static ABORT0,
     active0: &'static ACTIVE0,
     busy0: &'static BUSY0,
+    enableclr0: &'static ENABLECLR0,
     enableset0: &'static ENABLESET0,
     errint0: &'static ERRINT0,
     inta0: &'static INTA0,
@@ -139,8 +141,10 @@ where
             let registers = pac::DMA0::ptr();
 
             Self {
+                abort0: &(*registers).abort0,
                 active0: &(*registers).active0,
                 busy0: &(*registers).busy0,
+                enableclr0: &(*registers).enableclr0,
                 enableset0: &(*registers).enableset0,
                 errint0: &(*registers).errint0,
                 inta0: &(*registers).inta0,
@@ -175,6 +179,20 @@ where
         });
     }
 
+    pub(super) fn disable(&self) {
+        self.enableclr0.write(|w| {
+            // Sound, as all values assigned to `C::FLAG` are valid here.
+            unsafe { w.clr().bits(C::FLAG) }
+        });
+    }
+
+    pub(super) fn abort(&self) {
+        self.abort0.write(|w| {
+            // Sound, as all values assigned to `C::FLAG` are valid here.
+            unsafe { w.abortctrl().bits(C::FLAG) }
+        });
+    }
+
     pub(super) fn trigger(&self) {
         self.settrig0.write(|w| {
             // Sound, as all values assigned to `C::FLAG` are valid here.
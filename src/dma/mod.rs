@@ -20,7 +20,7 @@ pub use self::{
     channels::Channel,
     gen::*,
     peripheral::DMA,
-    transfer::{Dest, Payload, Source, Transfer},
+    transfer::{Dest, Payload, Source, Transfer, Word},
 };
 
 pub(crate) use self::buffer::Buffer;
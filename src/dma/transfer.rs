@@ -7,7 +7,7 @@ use core::{
 
 use crate::{
     init_state::Enabled,
-    pac::dma0::channel::xfercfg::{DSTINC_A, SRCINC_A},
+    pac::dma0::channel::xfercfg::{DSTINC_A, SRCINC_A, WIDTH_A},
 };
 
 use super::{
@@ -41,7 +41,7 @@ impl<C, S, D> Transfer<state::Ready, C, S, D>
 where
     C: Instance,
     S: Source,
-    D: Dest,
+    D: Dest<Word = S::Word>,
 {
     /// Create a new DMA transfer
     ///
@@ -95,7 +95,7 @@ where
             w.clrtrig().cleared();
             w.setinta().no_effect();
             w.setintb().no_effect();
-            w.width().bit_8();
+            w.width().variant(S::Word::WIDTH);
             w.srcinc().variant(source.increment());
             w.dstinc().variant(dest.increment());
             unsafe { w.xfercount().bits(transfer_count) }
@@ -258,6 +258,50 @@ where
         registers.b_interrupt_fired()
     }
 
+    /// Abort the transfer
+    ///
+    /// Stops the transfer, regardless of whether it has finished or not. This
+    /// is useful, if the number of words to be transferred is not known in
+    /// advance, for example when receiving from an SPI master.
+    ///
+    /// Returns the transfer payload, which contains all resources that were
+    /// held by this transfer, as well as the number of words that have _not_
+    /// been transferred.
+    ///
+    /// Unlike [`wait`], this method does not tell the source and destination
+    /// to finish the transfer.
+    ///
+    /// This method is only available, if the `Transfer` is in the [`Started`]
+    /// state. Code attempting to call this method when this is not the case
+    /// will not compile.
+    ///
+    /// [`wait`]: #method.wait
+    /// [`Started`]: state/struct.Started.html
+    pub fn abort(self) -> (Payload<C, S, D>, u16) {
+        let registers = SharedRegisters::<C>::new();
+
+        // The channel must be disabled and no longer busy, before it can be
+        // aborted. See the description of the ABORT0 register in the user
+        // manual.
+        registers.disable();
+        while registers.is_busy() {}
+
+        // XFERCOUNT is decremented after every transferred word, and reads as
+        // the number of remaining words minus one, while the channel is still
+        // active.
+        let remaining = if registers.is_active() {
+            self.payload.channel.xfercfg.read().xfercount().bits() + 1
+        } else {
+            0
+        };
+
+        registers.abort();
+
+        compiler_fence(Ordering::SeqCst);
+
+        (self.payload, remaining)
+    }
+
     /// Waits for the transfer to finish
     ///
     /// This method will block until the transfer is finished. If this is not
@@ -374,6 +418,9 @@ pub trait Source: crate::private::Sealed {
     /// The error that can occur while finishing the transfer
     type Error;
 
+    /// The size of the words that are read from the source
+    type Word: Word;
+
     /// Indicates whether the source is valid
     ///
     /// Buffers are valid, if they have a length of 1024 or less. Peripherals
@@ -418,6 +465,9 @@ pub trait Dest: crate::private::Sealed {
     /// The error that can occur while finishing the transfer
     type Error;
 
+    /// The size of the words that are written to the destination
+    type Word: Word;
+
     /// Indicates whether the destination is valid
     ///
     /// Buffers are valid if they have a length of 1024 or less. Peripherals are
@@ -453,6 +503,34 @@ pub trait Dest: crate::private::Sealed {
     fn finish(&mut self) -> nb::Result<(), Self::Error>;
 }
 
+/// Implemented for types that represent a supported transfer width
+///
+/// The transfer width of a [`Transfer`] is determined by the word size of its
+/// [`Source`] and [`Dest`], which must match. This trait is implemented for
+/// `u8` and `u16`.
+///
+/// This trait's items are intended for internal use only.
+///
+/// [`Transfer`]: struct.Transfer.html
+/// [`Source`]: trait.Source.html
+/// [`Dest`]: trait.Dest.html
+pub trait Word: crate::private::Sealed {
+    /// The value of XFERCFG.WIDTH for this word size
+    const WIDTH: WIDTH_A;
+}
+
+impl crate::private::Sealed for u8 {}
+
+impl Word for u8 {
+    const WIDTH: WIDTH_A = WIDTH_A::BIT_8;
+}
+
+impl crate::private::Sealed for u16 {}
+
+impl Word for u16 {
+    const WIDTH: WIDTH_A = WIDTH_A::BIT_16;
+}
+
 /// Types representing the states of a DMA transfer
 pub mod state {
     /// Indicates that a transfer is ready to be started
@@ -0,0 +1,408 @@
+//! Groups of GPIO pins that are accessed as one
+//!
+//! A [`Bus`] combines multiple [`GpioPin`]s into a parallel bus, whose value
+//! can be read or written as a whole. This is useful for parallel interfaces,
+//! like character LCDs or 8-bit data buses, where updating the pins one by one
+//! would cause glitches between the individual updates.
+//!
+//! Writes use the masked port registers (MASK/MPIN), which update all bus pins
+//! on a port in a single access, while leaving all other pins on that port
+//! untouched. On the LPC845, a bus can contain pins from both ports. The ports
+//! are then updated in two consecutive accesses, one for each port.
+//!
+//! Single pins of the bus are accessed through their byte pin (B) and word pin
+//! (W) registers, which don't require any masking or read-modify-write cycles.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     gpio::{self, Bus},
+//!     Peripherals,
+//! };
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! #[cfg(feature = "82x")]
+//! let gpio = p.GPIO;
+//! #[cfg(feature = "845")]
+//! let gpio = {
+//!     let mut syscon = p.SYSCON.split();
+//!     p.GPIO.enable(&mut syscon.handle)
+//! };
+//!
+//! let d0 = p.pins.pio0_14
+//!     .into_output_pin(gpio.tokens.pio0_14, gpio::Level::Low);
+//! let d1 = p.pins.pio0_15
+//!     .into_output_pin(gpio.tokens.pio0_15, gpio::Level::Low);
+//! let d2 = p.pins.pio0_16
+//!     .into_output_pin(gpio.tokens.pio0_16, gpio::Level::Low);
+//! let d3 = p.pins.pio0_17
+//!     .into_output_pin(gpio.tokens.pio0_17, gpio::Level::Low);
+//!
+//! // `d0` is bit 0 of the bus, `d3` is bit 3.
+//! let mut bus = Bus::new((d0, d1, d2, d3));
+//!
+//! // Sets `d0` and `d2` HIGH, and `d1` and `d3` LOW, in a single access.
+//! bus.write(0b0101);
+//! ```
+//!
+//! [`GpioPin`]: ../struct.GpioPin.html
+
+use core::ptr;
+
+use cortex_m::interrupt;
+
+use crate::{pac, pins};
+
+use super::{
+    direction::{self, Direction},
+    GpioPin, Level, Registers, PORTS,
+};
+
+/// A group of GPIO pins that are accessed as one
+///
+/// The pins are passed to [`Bus::new`] as a tuple of up to 16 [`GpioPin`]s,
+/// which must all have the same direction. The first pin in the tuple is bit 0
+/// of the bus, the second pin is bit 1, and so forth.
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// [`Bus::new`]: #method.new
+/// [`GpioPin`]: ../struct.GpioPin.html
+/// [module documentation]: index.html
+pub struct Bus<T> {
+    pins: T,
+    layout: [(u8, u8); 16],
+    width: u8,
+    masks: [u32; PORTS],
+    contiguous: Option<(u8, u8)>,
+}
+
+impl<T> Bus<T>
+where
+    T: Pins,
+{
+    /// Create a bus from a tuple of GPIO pins
+    pub fn new(pins: T) -> Self {
+        let mut layout = [(0, 0); 16];
+        let mut width = 0;
+        let mut masks = [0; PORTS];
+
+        pins.for_each(&mut |port, id| {
+            layout[width] = (port, id);
+            masks[usize::from(port)] |= 0x1 << id;
+            width += 1;
+        });
+
+        // If all pins are on the same port, in order and without gaps, values
+        // can be converted by shifting them, instead of by moving every bit.
+        let (first_port, first_id) = layout[0];
+        let contiguous = layout[..width]
+            .iter()
+            .zip(first_id..)
+            .all(|(&pin, id)| pin == (first_port, id))
+            .then_some((first_port, first_id));
+
+        Self {
+            pins,
+            layout,
+            width: width as u8,
+            masks,
+            contiguous,
+        }
+    }
+
+    /// Returns the number of pins in the bus
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// Read the current value of the bus
+    ///
+    /// Reads the pin levels of all ports that have bus pins in one access per
+    /// port. For output pins, this is the level that the pins are set to.
+    pub fn read(&self) -> u32 {
+        // Sound, as we're only reading from the PIN registers.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        let mut levels = [0; PORTS];
+        for (port, levels) in levels.iter_mut().enumerate() {
+            if self.masks[port] != 0 {
+                *levels = registers.pin[port].read().port().bits();
+            }
+        }
+
+        self.gather(&levels)
+    }
+
+    /// Read the level of a single pin of the bus
+    ///
+    /// Uses the word pin register of the pin, which reads as all ones, if the
+    /// pin is HIGH, and all zeros, if it is LOW.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is not smaller than the width of the bus.
+    pub fn read_bit(&self, index: u8) -> Level {
+        let (port, id) = self.layout()[usize::from(index)];
+
+        // The word pin registers are located at offset 0x1000 of the GPIO
+        // register block, one word per pin, 32 words per port. See user
+        // manual, section 9.6.2 (LPC82x) or 12.6.2 (LPC84x).
+        //
+        // This is sound, as we're only reading from the register.
+        let value = unsafe {
+            let w = (pac::GPIO::ptr() as *const u8).add(0x1000) as *const u32;
+            ptr::read_volatile(w.add(usize::from(port) * 32 + usize::from(id)))
+        };
+
+        match value {
+            0 => Level::Low,
+            _ => Level::High,
+        }
+    }
+
+    /// Return the pins, consuming the bus
+    pub fn free(self) -> T {
+        self.pins
+    }
+
+    fn scatter(&self, value: u32) -> [u32; PORTS] {
+        let mut values = [0; PORTS];
+
+        match self.contiguous {
+            Some((port, shift)) => {
+                values[usize::from(port)] = value << shift;
+            }
+            None => {
+                for (i, &(port, id)) in self.layout().iter().enumerate() {
+                    values[usize::from(port)] |= ((value >> i) & 0x1) << id;
+                }
+            }
+        }
+
+        for (value, mask) in values.iter_mut().zip(self.masks.iter()) {
+            *value &= mask;
+        }
+
+        values
+    }
+
+    fn gather(&self, levels: &[u32; PORTS]) -> u32 {
+        let value = match self.contiguous {
+            Some((port, shift)) => levels[usize::from(port)] >> shift,
+            None => self.layout().iter().enumerate().fold(
+                0,
+                |value, (i, &(port, id))| {
+                    value | ((levels[usize::from(port)] >> id) & 0x1) << i
+                },
+            ),
+        };
+
+        value & (u32::MAX >> (32 - self.width))
+    }
+
+    fn layout(&self) -> &[(u8, u8)] {
+        &self.layout[..usize::from(self.width)]
+    }
+}
+
+impl<T> Bus<T>
+where
+    T: Pins<Direction = direction::Output>,
+{
+    /// Write a value to the bus
+    ///
+    /// Bit 0 of `value` is written to the first pin of the bus, bit 1 to the
+    /// second pin, and so forth. Bits beyond the width of the bus are ignored.
+    ///
+    /// All bus pins on a port are updated in a single access, using the masked
+    /// port registers. Pins on different ports are updated in consecutive
+    /// accesses, starting with port 0.
+    pub fn write(&mut self, value: u32) {
+        let values = self.scatter(value);
+
+        // This is sound, as MASK and MPIN are only accessed from within
+        // critical sections, and MASK is always written before MPIN. MPIN only
+        // affects the pins that are not masked, which are exactly the pins
+        // owned by this bus.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        interrupt::free(|_| {
+            for (port, (&mask, &value)) in
+                self.masks.iter().zip(values.iter()).enumerate()
+            {
+                if mask == 0 {
+                    continue;
+                }
+
+                registers.mask[port]
+                    .write(|w| unsafe { w.maskp().bits(!mask) });
+                registers.mpin[port]
+                    .write(|w| unsafe { w.mportp().bits(value) });
+            }
+        });
+    }
+
+    /// Set the bus pins selected by `bits` to HIGH
+    ///
+    /// Leaves all other bus pins untouched.
+    pub fn set_bits(&mut self, bits: u32) {
+        let values = self.scatter(bits);
+
+        // Sound, as we only do stateless writes to bits owned by this bus.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        for (port, &value) in values.iter().enumerate() {
+            if value != 0 {
+                registers.set[port].write(|w| unsafe { w.setp().bits(value) });
+            }
+        }
+    }
+
+    /// Set the bus pins selected by `bits` to LOW
+    ///
+    /// Leaves all other bus pins untouched.
+    pub fn clear_bits(&mut self, bits: u32) {
+        let values = self.scatter(bits);
+
+        // Sound, as we only do stateless writes to bits owned by this bus.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        for (port, &value) in values.iter().enumerate() {
+            if value != 0 {
+                registers.clr[port].write(|w| unsafe { w.clrp().bits(value) });
+            }
+        }
+    }
+
+    /// Toggle the bus pins selected by `bits`
+    ///
+    /// Leaves all other bus pins untouched.
+    pub fn toggle_bits(&mut self, bits: u32) {
+        let values = self.scatter(bits);
+
+        // Sound, as we only do stateless writes to bits owned by this bus.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = Registers::new(gpio);
+
+        for (port, &value) in values.iter().enumerate() {
+            if value != 0 {
+                registers.not[port].write(|w| unsafe { w.notp().bits(value) });
+            }
+        }
+    }
+
+    /// Set a single pin of the bus to the given level
+    ///
+    /// Uses the byte pin register of the pin, which doesn't require a
+    /// read-modify-write cycle.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is not smaller than the width of the bus.
+    pub fn write_bit(&mut self, index: u8, level: Level) {
+        let (port, id) = self.layout()[usize::from(index)];
+        let value = match level {
+            Level::High => 1,
+            Level::Low => 0,
+        };
+
+        // The byte pin registers are located at the start of the GPIO register
+        // block, one byte per pin, 32 bytes per port. See user manual, section
+        // 9.6.1 (LPC82x) or 12.6.1 (LPC84x).
+        //
+        // This is sound, as the pin belongs to this bus, and writing to a byte
+        // pin register only affects that pin.
+        unsafe {
+            let b = (pac::GPIO::ptr() as *mut u8)
+                .add(usize::from(port) * 32 + usize::from(id));
+            ptr::write_volatile(b, value);
+        }
+    }
+}
+
+/// Implemented for tuples of GPIO pins that can form a [`Bus`]
+///
+/// This trait is implemented for tuples of 1 to 16 [`GpioPin`]s that all have
+/// the same direction. It is not intended to be implemented by users of this
+/// crate.
+///
+/// [`Bus`]: struct.Bus.html
+/// [`GpioPin`]: ../struct.GpioPin.html
+pub trait Pins: private::Sealed {
+    /// The direction of all pins in the tuple
+    type Direction: Direction;
+
+    /// Internal method to iterate over the port and pin numbers of all pins
+    ///
+    /// This method is for internal use only. Any changes to it won't be
+    /// considered breaking changes.
+    fn for_each(&self, f: &mut dyn FnMut(u8, u8));
+}
+
+macro_rules! impl_pins {
+    ($($P:ident: $i:tt),*) => {
+        impl<D, $($P,)*> private::Sealed for ($(GpioPin<$P, D>,)*) {}
+
+        impl<D, $($P,)*> Pins for ($(GpioPin<$P, D>,)*)
+        where
+            D: Direction,
+            $($P: pins::Trait,)*
+        {
+            type Direction = D;
+
+            fn for_each(&self, f: &mut dyn FnMut(u8, u8)) {
+                $(
+                    f(self.$i.inner().port(), self.$i.inner().id());
+                )*
+            }
+        }
+    };
+}
+
+impl_pins!(P0: 0);
+impl_pins!(P0: 0, P1: 1);
+impl_pins!(P0: 0, P1: 1, P2: 2);
+impl_pins!(P0: 0, P1: 1, P2: 2, P3: 3);
+impl_pins!(P0: 0, P1: 1, P2: 2, P3: 3, P4: 4);
+impl_pins!(P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5);
+impl_pins!(P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6);
+impl_pins!(P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7);
+impl_pins!(P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9
+);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9,
+    P10: 10
+);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9,
+    P10: 10, P11: 11
+);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9,
+    P10: 10, P11: 11, P12: 12
+);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9,
+    P10: 10, P11: 11, P12: 12, P13: 13
+);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9,
+    P10: 10, P11: 11, P12: 12, P13: 13, P14: 14
+);
+impl_pins!(
+    P0: 0, P1: 1, P2: 2, P3: 3, P4: 4, P5: 5, P6: 6, P7: 7, P8: 8, P9: 9,
+    P10: 10, P11: 11, P12: 12, P13: 13, P14: 14, P15: 15
+);
+
+mod private {
+    pub trait Sealed {}
+}
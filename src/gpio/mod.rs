@@ -2,7 +2,8 @@
 //!
 //! The entry point to this API is [`GPIO`]. It can be used to initialize the
 //! peripheral, and is required to convert instances of [`Pin`] to a
-//! [`GpioPin`], which provides the core GPIO API.
+//! [`GpioPin`], which provides the core GPIO API. Multiple [`GpioPin`]s can be
+//! combined into a [`Bus`], to access them as one.
 //!
 //! The GPIO peripheral is described in the following user manuals:
 //! - LPC82x user manual, chapter 9
@@ -39,8 +40,13 @@
 //! [`GPIO`]: struct.GPIO.html
 //! [`Pin`]: ../pins/struct.Pin.html
 //! [`GpioPin`]: struct.GpioPin.html
+//! [`Bus`]: bus/struct.Bus.html
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
+pub mod bus;
+
+pub use self::bus::Bus;
+
 use core::marker::PhantomData;
 
 use embedded_hal::digital::v2::{
@@ -56,11 +62,11 @@ use void::Void;
 use crate::{init_state, pac, pins, syscon};
 
 #[cfg(feature = "845")]
-use crate::pac::gpio::{CLR, DIRCLR, DIRSET, NOT, PIN, SET};
+use crate::pac::gpio::{CLR, DIRCLR, DIRSET, MASK, MPIN, NOT, PIN, SET};
 #[cfg(feature = "82x")]
 use crate::pac::gpio::{
-    CLR0 as CLR, DIRCLR0 as DIRCLR, DIRSET0 as DIRSET, NOT0 as NOT,
-    PIN0 as PIN, SET0 as SET,
+    CLR0 as CLR, DIRCLR0 as DIRCLR, DIRSET0 as DIRSET, MASK0 as MASK,
+    MPIN0 as MPIN, NOT0 as NOT, PIN0 as PIN, SET0 as SET,
 };
 
 use self::direction::{Direction, DynamicPinErr};
@@ -854,17 +860,17 @@ impl Level {
     }
 }
 
-fn set_high(registers: &Registers, inner: &impl pins::Trait) {
+pub(crate) fn set_high(registers: &Registers, inner: &impl pins::Trait) {
     registers.set[usize::from(inner.port())]
         .write(|w| unsafe { w.setp().bits(inner.mask()) });
 }
 
-fn set_low(registers: &Registers, inner: &impl pins::Trait) {
+pub(crate) fn set_low(registers: &Registers, inner: &impl pins::Trait) {
     registers.clr[usize::from(inner.port())]
         .write(|w| unsafe { w.clrp().bits(inner.mask()) });
 }
 
-fn is_high(registers: &Registers, inner: &impl pins::Trait) -> bool {
+pub(crate) fn is_high(registers: &Registers, inner: &impl pins::Trait) -> bool {
     registers.pin[usize::from(inner.port())]
         .read()
         .port()
@@ -876,7 +882,10 @@ fn is_high(registers: &Registers, inner: &impl pins::Trait) -> bool {
 // For internal use only.
 // Use the direction helpers of `GpioPin<P, direction::Output>` and `GpioPin<P, direction::Dynamic>`
 // instead.
-fn set_direction_output(registers: &Registers, inner: &impl pins::Trait) {
+pub(crate) fn set_direction_output(
+    registers: &Registers,
+    inner: &impl pins::Trait,
+) {
     registers.dirset[usize::from(inner.port())]
         .write(|w| unsafe { w.dirsetp().bits(inner.mask()) });
 }
@@ -884,16 +893,28 @@ fn set_direction_output(registers: &Registers, inner: &impl pins::Trait) {
 // For internal use only.
 // Use the direction helpers of `GpioPin<P, direction::Input>` and `GpioPin<P, direction::Dynamic>`
 // instead.
-fn set_direction_input(registers: &Registers, inner: &impl pins::Trait) {
+pub(crate) fn set_direction_input(
+    registers: &Registers,
+    inner: &impl pins::Trait,
+) {
     registers.dirclr[usize::from(inner.port())]
         .write(|w| unsafe { w.dirclrp().bits(inner.mask()) });
 }
 
+/// The number of GPIO ports
+#[cfg(feature = "82x")]
+pub(crate) const PORTS: usize = 1;
+/// The number of GPIO ports
+#[cfg(feature = "845")]
+pub(crate) const PORTS: usize = 2;
+
 /// This is an internal type that should be of no concern to users of this crate
 pub struct Registers<'gpio> {
     dirset: &'gpio [DIRSET],
     dirclr: &'gpio [DIRCLR],
+    mask: &'gpio [MASK],
     pin: &'gpio [PIN],
+    mpin: &'gpio [MPIN],
     set: &'gpio [SET],
     clr: &'gpio [CLR],
     not: &'gpio [NOT],
@@ -906,9 +927,11 @@ impl<'gpio> Registers<'gpio> {
     /// caller, accessing all registers is still completely race-free, as long
     /// as the following rules are upheld:
     /// - Never write to `pin`, only use it for reading.
+    /// - Only write to `mask` and `mpin` from within a critical section, and
+    ///   always write `mask` before accessing `mpin`.
     /// - For all other registers, only set bits that no other callers are
     ///   setting.
-    fn new(gpio: &'gpio pac::gpio::RegisterBlock) -> Self {
+    pub(crate) fn new(gpio: &'gpio pac::gpio::RegisterBlock) -> Self {
         #[cfg(feature = "82x")]
         {
             use core::slice;
@@ -916,7 +939,9 @@ impl<'gpio> Registers<'gpio> {
             Self {
                 dirset: slice::from_ref(&gpio.dirset0),
                 dirclr: slice::from_ref(&gpio.dirclr0),
+                mask: slice::from_ref(&gpio.mask0),
                 pin: slice::from_ref(&gpio.pin0),
+                mpin: slice::from_ref(&gpio.mpin0),
                 set: slice::from_ref(&gpio.set0),
                 clr: slice::from_ref(&gpio.clr0),
                 not: slice::from_ref(&gpio.not0),
@@ -927,7 +952,9 @@ impl<'gpio> Registers<'gpio> {
         Self {
             dirset: &gpio.dirset,
             dirclr: &gpio.dirclr,
+            mask: &gpio.mask,
             pin: &gpio.pin,
+            mpin: &gpio.mpin,
             set: &gpio.set,
             clr: &gpio.clr,
             not: &gpio.not,
@@ -1,6 +1,9 @@
 use core::marker::PhantomData;
 
-use crate::syscon::{self, clock_source::PeripheralClockSelector};
+use crate::{
+    clock::Frequency,
+    syscon::{self, clock_source::PeripheralClockSelector},
+};
 
 /// Contains the clock configuration for an I2C instance
 pub struct Clock<Clock> {
@@ -29,6 +32,220 @@ where
     }
 }
 
+#[cfg(feature = "82x")]
+impl Clock<()> {
+    /// Create the clock config for a given bus frequency
+    ///
+    /// I2C is always powered by the system clock on LPC82x. `scl_hz` is the
+    /// requested frequency of SCL. The configuration is computed from the
+    /// frequency of the system clock. See [`Timing::calculate`] for details.
+    ///
+    /// [`Timing::calculate`]: struct.Timing.html#method.calculate
+    pub fn from_frequency(
+        clock: &syscon::SystemClock,
+        scl_hz: u32,
+    ) -> Result<Self, ClockError> {
+        let timing = Timing::calculate(clock.hz(), scl_hz)?;
+
+        Ok(Self::new(
+            &(),
+            timing.divval,
+            timing.scl_high,
+            timing.scl_low,
+        ))
+    }
+}
+
+#[cfg(feature = "845")]
+impl<C> Clock<C>
+where
+    C: ClockSource + Frequency,
+{
+    /// Create the clock config for a given bus frequency
+    ///
+    /// `scl_hz` is the requested frequency of SCL. The configuration is
+    /// computed from the frequency of the clock source `clock`. See
+    /// [`Timing::calculate`] for details.
+    ///
+    /// [`Timing::calculate`]: struct.Timing.html#method.calculate
+    pub fn from_frequency(clock: &C, scl_hz: u32) -> Result<Self, ClockError> {
+        let timing = Timing::calculate(clock.hz(), scl_hz)?;
+
+        Ok(Self::new(
+            clock,
+            timing.divval,
+            timing.scl_high,
+            timing.scl_low,
+        ))
+    }
+}
+
+/// Timing configuration of the I2C clock
+///
+/// This is computed by [`Timing::calculate`], independently of any hardware,
+/// and can be used to create a [`Clock`] using [`Clock::from_frequency`].
+///
+/// [`Timing::calculate`]: #method.calculate
+/// [`Clock`]: struct.Clock.html
+/// [`Clock::from_frequency`]: struct.Clock.html#method.from_frequency
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Timing {
+    /// The value of the DIVVAL field
+    ///
+    /// The clock source is divided by `divval + 1` to get the I2C function
+    /// clock.
+    pub divval: u16,
+
+    /// The number of function clock cycles SCL is high (2-9)
+    pub scl_high: u8,
+
+    /// The number of function clock cycles SCL is low (2-9)
+    pub scl_low: u8,
+}
+
+impl Timing {
+    /// Compute the timing for a given bus frequency
+    ///
+    /// `clock_hz` is the frequency of the I2C clock source, `scl_hz` is the
+    /// requested frequency of SCL. The speed mode is determined from `scl_hz`:
+    /// Standard-mode up to 100 kHz, Fast-mode up to 400 kHz, Fast-mode Plus
+    /// up to 1 MHz. The minimum SCL high and low times of that mode, as
+    /// defined in the I2C specification, are always met.
+    ///
+    /// The resulting frequency never exceeds `scl_hz`, but may be lower, due
+    /// to the limited resolution of the clock configuration. The smallest
+    /// possible divider is used, to get as close to `scl_hz` as possible.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClockError::FrequencyOutOfRange`], if `scl_hz` is 0 or
+    /// larger than 1 MHz. Returns [`ClockError::ClockTooFast`], if the clock
+    /// source can't be divided down far enough. Returns
+    /// [`ClockError::ClockTooSlow`], if the resulting frequency would be more
+    /// than 10% below `scl_hz`.
+    ///
+    /// # Examples
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::i2c::{ClockError, Timing};
+    ///
+    /// let timing = Timing::calculate(12_000_000, 400_000).unwrap();
+    /// assert_eq!(timing.frequency(12_000_000), 400_000);
+    ///
+    /// let timing = Timing::calculate(12_000_000, 100_000).unwrap();
+    /// assert!(timing.frequency(12_000_000) <= 100_000);
+    ///
+    /// let timing = Timing::calculate(30_000_000, 1_000_000).unwrap();
+    /// assert_eq!(timing.frequency(30_000_000), 1_000_000);
+    ///
+    /// assert_eq!(
+    ///     Timing::calculate(3_000_000, 1_000_000),
+    ///     Err(ClockError::ClockTooSlow),
+    /// );
+    /// assert_eq!(
+    ///     Timing::calculate(12_000_000, 2_000_000),
+    ///     Err(ClockError::FrequencyOutOfRange),
+    /// );
+    /// ```
+    ///
+    /// [`ClockError::FrequencyOutOfRange`]: enum.ClockError.html#variant.FrequencyOutOfRange
+    /// [`ClockError::ClockTooFast`]: enum.ClockError.html#variant.ClockTooFast
+    /// [`ClockError::ClockTooSlow`]: enum.ClockError.html#variant.ClockTooSlow
+    pub fn calculate(clock_hz: u32, scl_hz: u32) -> Result<Self, ClockError> {
+        // Minimum low and high times of SCL in ns, for Standard-mode,
+        // Fast-mode, and Fast-mode Plus respectively.
+        let (low_ns, high_ns) = match scl_hz {
+            1..=100_000 => (4700, 4000),
+            100_001..=400_000 => (1300, 600),
+            400_001..=1_000_000 => (500, 260),
+            _ => return Err(ClockError::FrequencyOutOfRange),
+        };
+
+        const MIN: u32 = 2;
+        const MAX: u32 = 9;
+
+        let clock_hz = u64::from(clock_hz);
+        let scl_hz = u64::from(scl_hz);
+
+        // Start with the smallest divider that can get us at or below the
+        // requested frequency.
+        let first = clock_hz.div_ceil(scl_hz * u64::from(MAX * 2)).max(1);
+
+        for div in first..=0x1_0000 {
+            // Number of function clock cycles per SCL period, so we don't
+            // exceed the requested frequency.
+            let cycles = clock_hz.div_ceil(div * scl_hz) as u32;
+            if cycles > MAX * 2 {
+                continue;
+            }
+
+            let cycles_for = |ns: u64| {
+                let cycles = (ns * clock_hz).div_ceil(div * 1_000_000_000);
+                (cycles as u32).max(MIN)
+            };
+            let mut low = cycles_for(low_ns);
+            let mut high = cycles_for(high_ns);
+            if low > MAX || high > MAX {
+                continue;
+            }
+
+            // Distribute the remaining cycles, preferring the low time, as
+            // its minimum is higher in all modes.
+            let mut remaining = cycles.saturating_sub(low + high);
+            while remaining > 0 && (low < MAX || high < MAX) {
+                if low < MAX {
+                    low += 1;
+                } else {
+                    high += 1;
+                }
+                remaining -= 1;
+            }
+
+            let timing = Self {
+                divval: (div - 1) as u16,
+                scl_high: high as u8,
+                scl_low: low as u8,
+            };
+
+            let frequency = u64::from(timing.frequency(clock_hz as u32));
+            if frequency * 10 < scl_hz * 9 {
+                return Err(ClockError::ClockTooSlow);
+            }
+
+            return Ok(timing);
+        }
+
+        Err(ClockError::ClockTooFast)
+    }
+
+    /// Return the SCL frequency that results from this timing
+    ///
+    /// `clock_hz` is the frequency of the I2C clock source.
+    pub fn frequency(&self, clock_hz: u32) -> u32 {
+        let cycles = (u32::from(self.divval) + 1)
+            * (u32::from(self.scl_high) + u32::from(self.scl_low));
+        clock_hz / cycles
+    }
+}
+
+/// Error computing an I2C clock configuration
+///
+/// Returned by [`Timing::calculate`] and [`Clock::from_frequency`].
+///
+/// [`Timing::calculate`]: struct.Timing.html#method.calculate
+/// [`Clock::from_frequency`]: struct.Clock.html#method.from_frequency
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockError {
+    /// The requested frequency is 0 or larger than 1 MHz
+    FrequencyOutOfRange,
+
+    /// The clock source is too fast to be divided down to the frequency
+    ClockTooFast,
+
+    /// The clock source is too slow to reach the frequency
+    ClockTooSlow,
+}
+
 /// Implemented for I2C clock sources
 pub trait ClockSource: private::Sealed {
     /// Select the clock source
@@ -43,11 +43,17 @@ pub enum Error {
 
     /// An unencodable address was specified.
     ///
-    /// Currently, only seven-bit addressing is implemented.
+    /// Seven-bit addresses must not be larger than `0x7f`, ten-bit addresses
+    /// must not be larger than `0x3ff`.
     AddressOutOfRange,
 
     /// While in slave mode, an unknown state was detected
     UnknownSlaveState(u8),
+
+    /// Bus recovery failed
+    ///
+    /// A device is still holding SDA or SCL low after the recovery sequence.
+    BusRecoveryFailed,
 }
 
 impl Error {
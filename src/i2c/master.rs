@@ -6,7 +6,7 @@ use core::{
     marker::PhantomData,
 };
 
-use embedded_hal::blocking::i2c;
+use embedded_hal::blocking::i2c::{self, Operation};
 
 use crate::{
     dma::{self, transfer::state::Ready},
@@ -31,10 +31,20 @@ use super::{Error, Instance};
 /// # `embedded-hal` traits
 /// - [`embedded_hal::blocking::i2c::Read`] for blocking reads
 /// - [`embedded_hal::blocking::i2c::Write`] for blocking writes
+/// - [`embedded_hal::blocking::i2c::WriteRead`] for blocking writes, followed
+///   by a read after a repeated start
+/// - [`embedded_hal::blocking::i2c::Transactional`] for multiple reads and
+///   writes, separated by repeated starts
+///
+/// All of those use seven-bit addresses. Call [`Master::ten_bit`] to use the
+/// same traits with ten-bit addresses.
 ///
 /// [`I2C`]: ../struct.I2C.html
 /// [`embedded_hal::blocking::i2c::Read`]: #impl-Read
 /// [`embedded_hal::blocking::i2c::Write`]: #impl-Write
+/// [`embedded_hal::blocking::i2c::WriteRead`]: #impl-WriteRead
+/// [`embedded_hal::blocking::i2c::Transactional`]: #impl-Transactional
+/// [`Master::ten_bit`]: #method.ten_bit
 pub struct Master<I: Instance, State, ModeState> {
     _state: PhantomData<State>,
     _mode_state: PhantomData<ModeState>,
@@ -62,19 +72,35 @@ impl<I, C> Master<I, Enabled<PhantomData<C>>, Enabled>
 where
     I: Instance,
 {
+    /// Use ten-bit addresses with the `embedded-hal` traits
+    ///
+    /// Returns a [`TenBit`] instance that borrows this `Master`, and
+    /// implements the same traits for ten-bit addresses.
+    ///
+    /// [`TenBit`]: struct.TenBit.html
+    pub fn ten_bit(&mut self) -> TenBit<'_, I, C> {
+        TenBit(self)
+    }
+
     /// Writes the provided buffer using DMA
     ///
+    /// Accepts seven-bit addresses as `u8`. Use [`Address::TenBit`] to write
+    /// to a ten-bit address.
+    ///
     /// # Panics
     ///
     /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    ///
+    /// [`Address::TenBit`]: enum.Address.html#variant.TenBit
     pub fn write_all(
         mut self,
-        address: u8,
+        address: impl Into<Address>,
         buffer: &'static [u8],
         channel: dma::Channel<I::MstChannel, Enabled>,
     ) -> Result<dma::Transfer<Ready, I::MstChannel, &'static [u8], Self>, Error>
     {
-        self.start_operation(address, Rw::Write)?;
+        self.wait_for_state(State::Idle)?;
+        self.start_operation(address.into(), Rw::Write)?;
         self.wait_for_state(State::TxReady)?;
         self.mstctl.modify(|_, w| w.mstdma().enabled());
         Ok(dma::Transfer::new(channel, buffer, self))
@@ -82,18 +108,59 @@ where
 
     /// Reads until the provided buffer is full, using DMA
     ///
+    /// Accepts seven-bit addresses as `u8`. Use [`Address::TenBit`] to read
+    /// from a ten-bit address.
+    ///
     /// # Panics
     ///
     /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    ///
+    /// [`Address::TenBit`]: enum.Address.html#variant.TenBit
     pub fn read_all(
         mut self,
-        address: u8,
+        address: impl Into<Address>,
         buffer: &'static mut [u8],
         channel: dma::Channel<I::MstChannel, Enabled>,
     ) -> Result<
         dma::Transfer<Ready, I::MstChannel, Self, &'static mut [u8]>,
         Error,
     > {
+        self.wait_for_state(State::Idle)?;
+        self.start_operation(address.into(), Rw::Read)?;
+        self.mstctl.modify(|_, w| w.mstdma().enabled());
+        Ok(dma::Transfer::new(channel, self, buffer))
+    }
+
+    /// Writes `bytes`, then reads until the provided buffer is full using DMA
+    ///
+    /// The bytes are written without using DMA, which is intended for short
+    /// writes, like the register address of a sensor. The read then follows
+    /// after a repeated start, without a stop condition in between.
+    ///
+    /// Accepts seven-bit addresses as `u8`. Use [`Address::TenBit`] for
+    /// ten-bit addresses.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    ///
+    /// [`Address::TenBit`]: enum.Address.html#variant.TenBit
+    #[allow(clippy::type_complexity)]
+    pub fn write_read_all(
+        mut self,
+        address: impl Into<Address>,
+        bytes: &[u8],
+        buffer: &'static mut [u8],
+        channel: dma::Channel<I::MstChannel, Enabled>,
+    ) -> Result<
+        dma::Transfer<Ready, I::MstChannel, Self, &'static mut [u8]>,
+        Error,
+    > {
+        let address = address.into();
+
+        self.wait_for_state(State::Idle)?;
+        self.start_operation(address, Rw::Write)?;
+        self.write_bytes(bytes)?;
         self.start_operation(address, Rw::Read)?;
         self.mstctl.modify(|_, w| w.mstdma().enabled());
         Ok(dma::Transfer::new(channel, self, buffer))
@@ -121,12 +188,51 @@ where
         Ok(())
     }
 
-    fn start_operation(&mut self, address: u8, rw: Rw) -> Result<(), Error> {
-        Error::check_address(address)?;
-        self.wait_for_state(State::Idle)?;
+    /// Send a start condition and the address
+    ///
+    /// Sends a repeated start, if an operation is already in progress. The
+    /// caller is responsible for making sure the peripheral is in the correct
+    /// state.
+    fn start_operation(
+        &mut self,
+        address: Address,
+        rw: Rw,
+    ) -> Result<(), Error> {
+        address.check()?;
+
+        match address {
+            Address::SevenBit(address) => {
+                self.send_address((address << 1) | rw as u8);
+            }
+            Address::TenBit(address) => {
+                // The first byte contains the reserved prefix `0b11110`,
+                // followed by the two most significant bits of the address.
+                let header = 0b1111_0000 | ((address >> 7) as u8 & 0b110);
+
+                // The second byte, containing the rest of the address, is
+                // always written.
+                self.send_address(header | Rw::Write as u8);
+                self.wait_for_state(State::TxReady)?;
+                self.mstdat.write(|w| unsafe {
+                    // Sound, as all 8-bit values are accepted here.
+                    w.data().bits(address as u8)
+                });
+                self.mstctl.write(|w| w.mstcontinue().continue_());
+
+                // To read, we need a repeated start, followed by only the
+                // first byte with the R/W bit set.
+                if let Rw::Read = rw {
+                    self.wait_for_state(State::TxReady)?;
+                    self.send_address(header | Rw::Read as u8);
+                }
+            }
+        }
 
+        Ok(())
+    }
+
+    fn send_address(&mut self, address_rw: u8) {
         // Write address
-        let address_rw = (address << 1) | rw as u8;
         self.mstdat.write(|w| unsafe {
             // Sound, as all 8-bit values are accepted here.
             w.data().bits(address_rw)
@@ -134,6 +240,90 @@ where
 
         // Start operation
         self.mstctl.write(|w| w.mststart().start());
+    }
+
+    /// Write bytes, after the address has been sent
+    ///
+    /// Leaves the peripheral in the `TxReady` state.
+    fn write_bytes(&mut self, data: &[u8]) -> Result<(), Error> {
+        for &b in data {
+            self.wait_for_state(State::TxReady)?;
+
+            // Write byte
+            self.mstdat.write(|w| unsafe { w.data().bits(b) });
+
+            // Continue transmission
+            self.mstctl.write(|w| w.mstcontinue().continue_());
+        }
+
+        self.wait_for_state(State::TxReady)
+    }
+
+    /// Read bytes, after the address has been sent
+    ///
+    /// `continued` must be `true`, if the last operation also was a read. In
+    /// that case, the last byte has already been read, and the slave needs to
+    /// be told to send another one.
+    ///
+    /// Leaves the peripheral in the `RxReady` state.
+    fn read_bytes(
+        &mut self,
+        buffer: &mut [u8],
+        continued: bool,
+    ) -> Result<(), Error> {
+        for (i, b) in buffer.iter_mut().enumerate() {
+            if i != 0 || continued {
+                // Continue transmission
+                self.mstctl.write(|w| w.mstcontinue().continue_());
+            }
+
+            self.wait_for_state(State::RxReady)?;
+
+            // Read received byte
+            *b = self.mstdat.read().data().bits();
+        }
+
+        self.wait_for_state(State::RxReady)
+    }
+
+    /// Execute a number of operations
+    ///
+    /// Adjacent operations of the same type are merged. Operations of different
+    /// types are separated by a repeated start.
+    fn execute<'a>(
+        &mut self,
+        address: Address,
+        operations: impl IntoIterator<Item = Operation<'a>>,
+    ) -> Result<(), Error> {
+        let mut previous = None;
+
+        for operation in operations {
+            let rw = match operation {
+                Operation::Read(_) => Rw::Read,
+                Operation::Write(_) => Rw::Write,
+            };
+
+            if previous.is_none() {
+                self.wait_for_state(State::Idle)?;
+            }
+            if previous != Some(rw) {
+                self.start_operation(address, rw)?;
+            }
+
+            match operation {
+                Operation::Read(buffer) => {
+                    self.read_bytes(buffer, previous == Some(Rw::Read))?
+                }
+                Operation::Write(data) => self.write_bytes(data)?,
+            }
+
+            previous = Some(rw);
+        }
+
+        if previous.is_some() {
+            // Stop operation
+            self.mstctl.write(|w| w.mststop().stop());
+        }
 
         Ok(())
     }
@@ -169,21 +359,7 @@ where
     ///
     /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.1/embedded_hal/blocking/i2c/trait.Write.html#tymethod.write
     fn write(&mut self, address: u8, data: &[u8]) -> Result<(), Self::Error> {
-        self.start_operation(address, Rw::Write)?;
-
-        for &b in data {
-            self.wait_for_state(State::TxReady)?;
-
-            // Write byte
-            self.mstdat.write(|w| unsafe { w.data().bits(b) });
-
-            // Continue transmission
-            self.mstctl.write(|w| w.mstcontinue().continue_());
-        }
-
-        self.finish_write()?;
-
-        Ok(())
+        self.execute(address.into(), [Operation::Write(data)])
     }
 }
 
@@ -203,23 +379,135 @@ where
         address: u8,
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.start_operation(address, Rw::Read)?;
+        self.execute(address.into(), [Operation::Read(buffer)])
+    }
+}
 
-        for (i, b) in buffer.iter_mut().enumerate() {
-            if i != 0 {
-                // Continue transmission
-                self.mstctl.write(|w| w.mstcontinue().continue_());
-            }
+impl<I, C> i2c::WriteRead for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
 
-            self.wait_for_state(State::RxReady)?;
+    /// Write to the I2C bus, then read from it after a repeated start
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.7/embedded_hal/blocking/i2c/trait.WriteRead.html#tymethod.write_read
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.execute(
+            address.into(),
+            [Operation::Write(bytes), Operation::Read(buffer)],
+        )
+    }
+}
 
-            // Read received byte
-            *b = self.mstdat.read().data().bits();
-        }
+impl<I, C> i2c::Transactional for Master<I, Enabled<PhantomData<C>>, Enabled>
+where
+    I: Instance,
+{
+    type Error = Error;
 
-        self.finish_read()?;
+    /// Execute the provided operations, separated by repeated starts
+    ///
+    /// Please refer to the [embedded-hal documentation] for details.
+    ///
+    /// [embedded-hal documentation]: https://docs.rs/embedded-hal/0.2.7/embedded_hal/blocking/i2c/trait.Transactional.html#tymethod.exec
+    fn exec<'a>(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'a>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address.into(), operations.iter_mut().map(reborrow))
+    }
+}
 
-        Ok(())
+/// Ten-bit addressing for the `embedded-hal` traits
+///
+/// Implements the same `embedded-hal` traits as [`Master`], but for ten-bit
+/// addresses. You can get an instance of this struct by calling
+/// [`Master::ten_bit`].
+///
+/// [`Master`]: struct.Master.html
+/// [`Master::ten_bit`]: struct.Master.html#method.ten_bit
+pub struct TenBit<'a, I: Instance, C>(
+    &'a mut Master<I, Enabled<PhantomData<C>>, Enabled>,
+);
+
+impl<I, C> i2c::Write<u16> for TenBit<'_, I, C>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn write(&mut self, address: u16, data: &[u8]) -> Result<(), Self::Error> {
+        self.0
+            .execute(Address::TenBit(address), [Operation::Write(data)])
+    }
+}
+
+impl<I, C> i2c::Read<u16> for TenBit<'_, I, C>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn read(
+        &mut self,
+        address: u16,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.0
+            .execute(Address::TenBit(address), [Operation::Read(buffer)])
+    }
+}
+
+impl<I, C> i2c::WriteRead<u16> for TenBit<'_, I, C>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn write_read(
+        &mut self,
+        address: u16,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.0.execute(
+            Address::TenBit(address),
+            [Operation::Write(bytes), Operation::Read(buffer)],
+        )
+    }
+}
+
+impl<I, C> i2c::Transactional<u16> for TenBit<'_, I, C>
+where
+    I: Instance,
+{
+    type Error = Error;
+
+    fn exec<'a>(
+        &mut self,
+        address: u16,
+        operations: &mut [Operation<'a>],
+    ) -> Result<(), Self::Error> {
+        self.0.execute(
+            Address::TenBit(address),
+            operations.iter_mut().map(reborrow),
+        )
+    }
+}
+
+fn reborrow<'a>(operation: &'a mut Operation) -> Operation<'a> {
+    match operation {
+        Operation::Read(buffer) => Operation::Read(buffer),
+        Operation::Write(data) => Operation::Write(data),
     }
 }
 
@@ -233,6 +521,7 @@ where
     I: Instance,
 {
     type Error = Error;
+    type Word = u8;
 
     fn is_valid(&self) -> bool {
         true
@@ -268,6 +557,7 @@ where
     I: Instance,
 {
     type Error = Error;
+    type Word = u8;
 
     fn is_valid(&self) -> bool {
         true
@@ -314,7 +604,38 @@ where
     }
 }
 
+/// An I2C slave address
+///
+/// Seven-bit addresses can be converted from `u8`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Address {
+    /// A seven-bit address
+    SevenBit(u8),
+
+    /// A ten-bit address
+    TenBit(u16),
+}
+
+impl Address {
+    fn check(&self) -> Result<(), Error> {
+        match *self {
+            Address::SevenBit(address) => Error::check_address(address),
+            Address::TenBit(address) if address > 0x3ff => {
+                Err(Error::AddressOutOfRange)
+            }
+            Address::TenBit(_) => Ok(()),
+        }
+    }
+}
+
+impl From<u8> for Address {
+    fn from(address: u8) -> Self {
+        Self::SevenBit(address)
+    }
+}
+
 /// Private helper struct to model the R/W bit
+#[derive(Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
 enum Rw {
     Write = 0,
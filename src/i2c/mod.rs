@@ -71,14 +71,16 @@ mod interrupts;
 mod peripheral;
 
 pub mod master;
+pub mod monitor;
 pub mod slave;
 
 pub use self::{
-    clock::{Clock, ClockSource},
+    clock::{Clock, ClockError, ClockSource, Timing},
     error::Error,
     instances::Instance,
     interrupts::Interrupts,
     master::Master,
+    monitor::Monitor,
     peripheral::I2C,
     slave::Slave,
 };
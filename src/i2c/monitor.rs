@@ -0,0 +1,283 @@
+//! API for the I2C monitor mode
+
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    init_state,
+    pac::i2c0::MONRXDAT,
+    reg_proxy::{Reg, RegProxy},
+};
+
+use super::{Error, Instance};
+
+/// API for I2C monitor mode
+///
+/// You can get access to this struct through the [`I2C`] struct. Monitor mode
+/// is enabled using [`I2C::enable_monitor_mode`].
+///
+/// In monitor mode, the I2C peripheral passively observes all traffic on the
+/// bus, regardless of whether it is the master, the slave, or neither. The
+/// monitor function has no DMA request of its own, so captured traffic needs
+/// to be read by software, typically from the interrupt handler for the
+/// "monitor ready" interrupt. [`Capture`] can be used to store the captured
+/// traffic until it can be processed.
+///
+/// This struct has two type parameters that track its state:
+/// - `State` tracks whether the I2C instance is enabled.
+/// - `ModeState` tracks whether the monitor mode is enabled.
+///
+/// [`I2C`]: ../struct.I2C.html
+/// [`I2C::enable_monitor_mode`]: ../struct.I2C.html#method.enable_monitor_mode
+/// [`Capture`]: struct.Capture.html
+pub struct Monitor<I: Instance, State, ModeState> {
+    _state: PhantomData<State>,
+    _mode_state: PhantomData<ModeState>,
+
+    monrxdat: RegProxy<MonRxDat<I>>,
+}
+
+impl<I, State, ModeState> Monitor<I, State, ModeState>
+where
+    I: Instance,
+{
+    pub(super) fn new() -> Self {
+        Self {
+            _state: PhantomData,
+            _mode_state: PhantomData,
+
+            monrxdat: RegProxy::new(),
+        }
+    }
+}
+
+impl<I, C> Monitor<I, init_state::Enabled<PhantomData<C>>, init_state::Enabled>
+where
+    I: Instance,
+{
+    /// Read the next event from the bus
+    ///
+    /// Returns `nb::Error::WouldBlock`, if nothing has been observed since the
+    /// last call. Returns [`Error::MonitorOverflow`], if data was lost because
+    /// it wasn't read in time.
+    ///
+    /// [`Error::MonitorOverflow`]: ../enum.Error.html#variant.MonitorOverflow
+    pub fn read(&mut self) -> nb::Result<Event, Error> {
+        // Sound, as we're only reading from the STAT register, and the MONIDLE
+        // flag is exclusively cleared by `Monitor`.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        Error::read::<I>()?;
+
+        let stat = i2c.stat.read();
+
+        if stat.monrdy().bit_is_set() {
+            let data = self.monrxdat.read();
+
+            let byte = data.monrxdat().bits();
+            let acknowledged = data.monnack().is_acknowledged();
+
+            let repeated_start = data.monrestart().is_detected();
+            if data.monstart().is_start_detected() || repeated_start {
+                return Ok(Event::Address {
+                    address: byte >> 1,
+                    read: byte & 0x1 == 0x1,
+                    repeated_start,
+                    acknowledged,
+                });
+            }
+
+            return Ok(Event::Data {
+                data: byte,
+                acknowledged,
+            });
+        }
+
+        if stat.monidle().bit_is_set() {
+            i2c.stat.write(|w| w.monidle().set_bit());
+            return Ok(Event::Stop);
+        }
+
+        Err(nb::Error::WouldBlock)
+    }
+}
+
+// Can't derive, because peripheral structs from the PAC don't implement
+// `Debug`. See https://github.com/rust-embedded/svd2rust/issues/48.
+impl<I, State, ModeState> fmt::Debug for Monitor<I, State, ModeState>
+where
+    I: Instance,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Monitor")
+            .field("_state", &self._state)
+            .field("_mode_state", &self._mode_state)
+            .field("monrxdat", &self.monrxdat)
+            .finish()
+    }
+}
+
+/// An event observed on the bus
+///
+/// Returned by [`Monitor::read`].
+///
+/// [`Monitor::read`]: struct.Monitor.html#method.read
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A start condition, followed by an address
+    Address {
+        /// The 7-bit address
+        ///
+        /// For ten-bit addresses, this contains the first address byte, the
+        /// rest of the address follows as an [`Event::Data`].
+        ///
+        /// [`Event::Data`]: #variant.Data
+        address: u8,
+
+        /// Indicates whether the master is reading from the slave
+        read: bool,
+
+        /// Indicates whether this was a repeated start condition
+        repeated_start: bool,
+
+        /// Indicates whether the address was acknowledged
+        acknowledged: bool,
+    },
+
+    /// A data byte
+    Data {
+        /// The data byte
+        data: u8,
+
+        /// Indicates whether the data byte was acknowledged
+        acknowledged: bool,
+    },
+
+    /// The bus has become idle
+    ///
+    /// This usually follows a stop condition.
+    Stop,
+}
+
+/// Ring buffer for captured bus traffic
+///
+/// Stores up to `N` events that were read from a [`Monitor`]. If the buffer is
+/// full, new events are dropped, and the number of dropped events is counted.
+///
+/// Since `new` is a `const fn`, this struct can be placed into a `static`
+/// and shared between the interrupt handler and the rest of the program.
+///
+/// [`Monitor`]: struct.Monitor.html
+#[derive(Debug)]
+pub struct Capture<const N: usize> {
+    events: [Event; N],
+    head: usize,
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> Capture<N> {
+    /// Create a new, empty, instance of `Capture`
+    pub const fn new() -> Self {
+        Self {
+            events: [Event::Stop; N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Capture all events that are currently available from the monitor
+    ///
+    /// Returns an error, if the monitor reports one. Any events that were
+    /// captured before that remain in the buffer.
+    pub fn poll<I, C>(
+        &mut self,
+        monitor: &mut Monitor<
+            I,
+            init_state::Enabled<PhantomData<C>>,
+            init_state::Enabled,
+        >,
+    ) -> Result<(), Error>
+    where
+        I: Instance,
+    {
+        loop {
+            match monitor.read() {
+                Ok(event) => self.push(event),
+                Err(nb::Error::WouldBlock) => return Ok(()),
+                Err(nb::Error::Other(err)) => return Err(err),
+            }
+        }
+    }
+
+    /// Add an event to the buffer
+    ///
+    /// Drops the event, if the buffer is full.
+    pub fn push(&mut self, event: Event) {
+        if self.len == N {
+            self.dropped = self.dropped.wrapping_add(1);
+            return;
+        }
+
+        self.events[(self.head + self.len) % N] = event;
+        self.len += 1;
+    }
+
+    /// Remove the oldest event from the buffer
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let event = self.events[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+
+        Some(event)
+    }
+
+    /// Return the number of events in the buffer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indicates whether the buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the number of events that were dropped because the buffer was full
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+impl<const N: usize> Default for Capture<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct MonRxDat<I>(PhantomData<I>);
+
+// Sound, as the pointer returned is valid for the duration of the program.
+unsafe impl<I> Reg for MonRxDat<I>
+where
+    I: Instance,
+{
+    type Target = MONRXDAT;
+
+    fn get() -> *const Self::Target {
+        // Sound, as MONRXDAT is exclusively used by `Monitor`, and only one
+        // `RegProxy` instance for it exists.
+        unsafe { &(*I::REGISTERS).monrxdat as *const _ }
+    }
+}
+
+// Can't derive, because peripheral structs from the PAC don't implement
+// `Debug`. See https://github.com/rust-embedded/svd2rust/issues/48.
+impl<I> fmt::Debug for MonRxDat<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MonRxDat(...)")
+    }
+}
@@ -1,21 +1,34 @@
 use core::{fmt, marker::PhantomData};
 
-use crate::{init_state, swm, syscon};
+use embedded_hal::blocking::delay::DelayUs;
 
-use super::{Clock, ClockSource, Error, Instance, Interrupts, Master, Slave};
+use crate::{gpio, init_state, pac, pins, swm, syscon};
+
+use super::{
+    Clock, ClockSource, Error, Instance, Interrupts, Master, Monitor, Slave,
+};
 
 /// Interface to an I2C peripheral
 ///
 /// Please refer to the [module documentation] for more information.
 ///
 /// [module documentation]: index.html
-pub struct I2C<I: Instance, State, MasterMode, SlaveMode> {
+pub struct I2C<
+    I: Instance,
+    State,
+    MasterMode,
+    SlaveMode,
+    MonitorMode = init_state::Disabled,
+> {
     /// API for I2C master mode
     pub master: Master<I, State, MasterMode>,
 
     /// API for I2C slave mode
     pub slave: Slave<I, State, SlaveMode>,
 
+    /// API for I2C monitor mode
+    pub monitor: Monitor<I, State, MonitorMode>,
+
     i2c: I,
 }
 
@@ -27,6 +40,7 @@ where
         I2C {
             master: Master::new(),
             slave: Slave::new(),
+            monitor: Monitor::new(),
 
             i2c: i2c,
         }
@@ -64,14 +78,21 @@ where
         I2C {
             master: Master::new(),
             slave: Slave::new(),
+            monitor: Monitor::new(),
 
             i2c: self.i2c,
         }
     }
 }
 
-impl<I, C, SlaveMode>
-    I2C<I, init_state::Enabled<PhantomData<C>>, init_state::Disabled, SlaveMode>
+impl<I, C, SlaveMode, MonitorMode>
+    I2C<
+        I,
+        init_state::Enabled<PhantomData<C>>,
+        init_state::Disabled,
+        SlaveMode,
+        MonitorMode,
+    >
 where
     I: Instance,
 {
@@ -101,6 +122,7 @@ where
         init_state::Enabled<PhantomData<C>>,
         init_state::Enabled,
         SlaveMode,
+        MonitorMode,
     > {
         // Set I2C clock frequency
         self.i2c
@@ -118,18 +140,20 @@ where
         I2C {
             master: Master::new(),
             slave: Slave::new(),
+            monitor: Monitor::new(),
 
             i2c: self.i2c,
         }
     }
 }
 
-impl<I, C, MasterMode>
+impl<I, C, MasterMode, MonitorMode>
     I2C<
         I,
         init_state::Enabled<PhantomData<C>>,
         MasterMode,
         init_state::Disabled,
+        MonitorMode,
     >
 where
     I: Instance,
@@ -142,6 +166,7 @@ where
     ///
     /// Consumes this instance of `I2C` and returns another instance that has
     /// its type state updated.
+    #[allow(clippy::type_complexity)]
     pub fn enable_slave_mode(
         self,
         address: u8,
@@ -151,6 +176,7 @@ where
             init_state::Enabled<PhantomData<C>>,
             MasterMode,
             init_state::Enabled,
+            MonitorMode,
         >,
         (Error, Self),
     > {
@@ -173,6 +199,7 @@ where
         Ok(I2C {
             master: Master::new(),
             slave: Slave::new(),
+            monitor: Monitor::new(),
 
             i2c: self.i2c,
         })
@@ -180,7 +207,110 @@ where
 }
 
 impl<I, C, MasterMode, SlaveMode>
-    I2C<I, init_state::Enabled<PhantomData<C>>, MasterMode, SlaveMode>
+    I2C<
+        I,
+        init_state::Enabled<PhantomData<C>>,
+        MasterMode,
+        SlaveMode,
+        init_state::Disabled,
+    >
+where
+    I: Instance,
+{
+    /// Enable monitor mode
+    ///
+    /// This method is only available, if the I2C instance is enabled, but
+    /// monitor mode is disabled. Code that attempts to call this method when
+    /// this is not the case will not compile.
+    ///
+    /// In monitor mode, the I2C peripheral passively observes all traffic on
+    /// the bus. Monitor mode can be used alongside master and slave mode. See
+    /// [`Monitor`] for how to access the captured traffic.
+    ///
+    /// If `clock_stretching` is `true`, the monitor stretches the clock, until
+    /// the captured data has been read. This prevents overflows, but affects
+    /// the timing of the bus.
+    ///
+    /// Consumes this instance of `I2C` and returns another instance that has
+    /// its type state updated.
+    ///
+    /// [`Monitor`]: monitor/struct.Monitor.html
+    pub fn enable_monitor_mode(
+        self,
+        clock_stretching: bool,
+    ) -> I2C<
+        I,
+        init_state::Enabled<PhantomData<C>>,
+        MasterMode,
+        SlaveMode,
+        init_state::Enabled,
+    > {
+        self.i2c.cfg.modify(|_, w| {
+            w.monclkstr().bit(clock_stretching);
+            w.monen().enabled()
+        });
+
+        I2C {
+            master: Master::new(),
+            slave: Slave::new(),
+            monitor: Monitor::new(),
+
+            i2c: self.i2c,
+        }
+    }
+}
+
+impl<I, C, MasterMode, SlaveMode>
+    I2C<
+        I,
+        init_state::Enabled<PhantomData<C>>,
+        MasterMode,
+        SlaveMode,
+        init_state::Enabled,
+    >
+where
+    I: Instance,
+{
+    /// Disable monitor mode
+    ///
+    /// This method is only available, if monitor mode is enabled. Code that
+    /// attempts to call this method when this is not the case will not
+    /// compile.
+    ///
+    /// Consumes this instance of `I2C` and returns another instance that has
+    /// its type state updated.
+    pub fn disable_monitor_mode(
+        self,
+    ) -> I2C<
+        I,
+        init_state::Enabled<PhantomData<C>>,
+        MasterMode,
+        SlaveMode,
+        init_state::Disabled,
+    > {
+        self.i2c.cfg.modify(|_, w| {
+            w.monclkstr().disabled();
+            w.monen().disabled()
+        });
+
+        I2C {
+            master: Master::new(),
+            slave: Slave::new(),
+            monitor: Monitor::new(),
+
+            i2c: self.i2c,
+        }
+    }
+}
+
+impl<I, C, MasterMode, SlaveMode, MonitorMode>
+    I2C<
+        I,
+        init_state::Enabled<PhantomData<C>>,
+        MasterMode,
+        SlaveMode,
+        MonitorMode,
+    >
 where
     I: Instance,
 {
@@ -200,6 +330,141 @@ where
         interrupts.disable(&self.i2c);
     }
 
+    /// Enable the timeout
+    ///
+    /// Once enabled, the I2C peripheral reports [`Error::EventTimeout`], if the
+    /// bus doesn't change state within the timeout, and [`Error::SclTimeout`],
+    /// if SCL is held low for longer than the timeout. Any operation that is
+    /// waiting for the bus returns these errors, instead of waiting forever.
+    /// Use [`enable_interrupts`] to get notified of timeouts via interrupt.
+    ///
+    /// `clocks` is the timeout in cycles of the I2C function clock, after the
+    /// clock divider has been applied. It is rounded up to the next multiple
+    /// of 16.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `clocks` is 0 or larger than 65536.
+    ///
+    /// [`Error::EventTimeout`]: enum.Error.html#variant.EventTimeout
+    /// [`Error::SclTimeout`]: enum.Error.html#variant.SclTimeout
+    /// [`enable_interrupts`]: #method.enable_interrupts
+    pub fn enable_timeout(&mut self, clocks: u32) {
+        assert!(clocks > 0 && clocks <= 65536);
+
+        // The timeout is (TO + 1) * 16 clocks.
+        let to = (clocks - 1) / 16;
+
+        // Sound, as we've made sure that the value fits into the 12-bit field.
+        self.i2c
+            .timeout
+            .write(|w| unsafe { w.to().bits(to as u16) });
+        self.i2c.cfg.modify(|_, w| w.timeouten().enabled());
+    }
+
+    /// Disable the timeout
+    pub fn disable_timeout(&mut self) {
+        self.i2c.cfg.modify(|_, w| w.timeouten().disabled());
+    }
+
+    /// Attempt to free a stuck bus
+    ///
+    /// If a slave was interrupted in the middle of a transfer, for example by
+    /// a reset of the master, it might hold SDA low indefinitely. This method
+    /// temporarily takes control of the SCL and SDA pins through GPIO, clocks
+    /// out up to nine pulses on SCL until the slave releases SDA, then issues
+    /// a stop condition. Afterwards, the I2C functions are assigned to the
+    /// pins again, and both pins are left as GPIO inputs.
+    ///
+    /// `scl` and `sda` must be the pins that the I2C functions of this
+    /// instance are assigned to. Both lines need to have pull-up resistors,
+    /// as they are only ever driven low. SCL runs at about 100 kHz.
+    ///
+    /// Returns [`Error::BusRecoveryFailed`], if either line is still held low
+    /// after the recovery sequence.
+    ///
+    /// [`Error::BusRecoveryFailed`]: enum.Error.html#variant.BusRecoveryFailed
+    pub fn recover_bus<SclPin, SclState, SdaPin, SdaState>(
+        &mut self,
+        scl: &pins::Pin<SclPin, SclState>,
+        _scl_token: &pins::Token<SclPin, init_state::Enabled>,
+        sda: &pins::Pin<SdaPin, SdaState>,
+        _sda_token: &pins::Token<SdaPin, init_state::Enabled>,
+        swm: &mut swm::Handle,
+        delay: &mut impl DelayUs<u32>,
+    ) -> Result<(), Error>
+    where
+        SclPin: pins::Trait,
+        SclState: pins::State,
+        SdaPin: pins::Trait,
+        SdaState: pins::State,
+    {
+        const HALF_PERIOD_US: u32 = 5;
+
+        // Sound, as we're only writing to the bits that belong to the SCL and
+        // SDA pins, and the tokens prove that no `GpioPin` instance for them
+        // exists.
+        let gpio = unsafe { &*pac::GPIO::ptr() };
+        let registers = gpio::Registers::new(gpio);
+
+        let scl = &scl.ty;
+        let sda = &sda.ty;
+
+        // Disable all I2C functions, so the peripheral doesn't get confused by
+        // what's happening on the bus.
+        let cfg = self.i2c.cfg.read().bits();
+        self.i2c.cfg.modify(|_, w| {
+            w.msten().disabled();
+            w.slven().disabled();
+            w.monen().disabled()
+        });
+
+        let detached = swm.detach(&[scl, sda]);
+
+        // Both pins are inputs, releasing the lines. Setting the output level
+        // to low means switching a pin to output pulls its line low.
+        gpio::set_direction_input(&registers, scl);
+        gpio::set_direction_input(&registers, sda);
+        gpio::set_low(&registers, scl);
+        gpio::set_low(&registers, sda);
+
+        for _ in 0..9 {
+            if gpio::is_high(&registers, sda) {
+                break;
+            }
+
+            gpio::set_direction_output(&registers, scl);
+            delay.delay_us(HALF_PERIOD_US);
+            gpio::set_direction_input(&registers, scl);
+            delay.delay_us(HALF_PERIOD_US);
+        }
+
+        // Stop condition: SDA goes high while SCL is high.
+        gpio::set_direction_output(&registers, scl);
+        delay.delay_us(HALF_PERIOD_US);
+        gpio::set_direction_output(&registers, sda);
+        delay.delay_us(HALF_PERIOD_US);
+        gpio::set_direction_input(&registers, scl);
+        delay.delay_us(HALF_PERIOD_US);
+        gpio::set_direction_input(&registers, sda);
+        delay.delay_us(HALF_PERIOD_US);
+
+        let released =
+            gpio::is_high(&registers, scl) && gpio::is_high(&registers, sda);
+
+        swm.reattach(detached);
+
+        // Sound, as we're writing back a value that we read from the same
+        // register.
+        self.i2c.cfg.write(|w| unsafe { w.bits(cfg) });
+
+        if !released {
+            return Err(Error::BusRecoveryFailed);
+        }
+
+        Ok(())
+    }
+
     /// Read and clear a detected error
     ///
     /// The `read` and `write` methods will return an error and clear it, if one
@@ -213,7 +478,8 @@ where
     }
 }
 
-impl<I, State, MasterMode, SlaveMode> I2C<I, State, MasterMode, SlaveMode>
+impl<I, State, MasterMode, SlaveMode, MonitorMode>
+    I2C<I, State, MasterMode, SlaveMode, MonitorMode>
 where
     I: Instance,
 {
@@ -236,8 +502,8 @@ where
 
 // Can't derive, because peripheral structs from the PAC don't implement
 // `Debug`. See https://github.com/rust-embedded/svd2rust/issues/48.
-impl<I, State, MasterMode, SlaveMode> fmt::Debug
-    for I2C<I, State, MasterMode, SlaveMode>
+impl<I, State, MasterMode, SlaveMode, MonitorMode> fmt::Debug
+    for I2C<I, State, MasterMode, SlaveMode, MonitorMode>
 where
     I: Instance,
 {
@@ -245,6 +511,7 @@ where
         f.debug_struct("I2C")
             .field("master", &self.master)
             .field("slave", &self.slave)
+            .field("monitor", &self.monitor)
             .field("i2c", &"i2c")
             .finish()
     }
@@ -46,6 +46,108 @@ impl<I, C> Slave<I, init_state::Enabled<PhantomData<C>>, init_state::Enabled>
 where
     I: Instance,
 {
+    /// Set one of the slave addresses
+    ///
+    /// The I2C peripheral can respond to up to four slave addresses. The
+    /// address at index 0 is set by [`I2C::enable_slave_mode`], but can be
+    /// changed here too. Use [`AddressMatched::index`] to find out which of the
+    /// addresses has been matched.
+    ///
+    /// Returns [`Error::AddressOutOfRange`], if `address` is not a valid 7-bit
+    /// address.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is larger than 3.
+    ///
+    /// [`I2C::enable_slave_mode`]: ../struct.I2C.html#method.enable_slave_mode
+    /// [`AddressMatched::index`]: struct.AddressMatched.html#method.index
+    /// [`Error::AddressOutOfRange`]: ../enum.Error.html#variant.AddressOutOfRange
+    pub fn set_address(
+        &mut self,
+        index: usize,
+        address: u8,
+    ) -> Result<(), Error> {
+        assert!(index < 4);
+        Error::check_address(address)?;
+
+        // Sound, as SLVADR is only accessed by `Slave` after slave mode has
+        // been enabled, and we have a mutable reference to it.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        i2c.slvadr[index].write(|w| {
+            w.sadisable().enabled();
+
+            // Sound, as all possible 7-bit values are acceptable here.
+            unsafe { w.slvadr().bits(address) }
+        });
+
+        Ok(())
+    }
+
+    /// Disable one of the slave addresses
+    ///
+    /// The slave will no longer respond to the address at `index`, until it is
+    /// set again using [`set_address`].
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `index` is larger than 3.
+    ///
+    /// [`set_address`]: #method.set_address
+    pub fn disable_address(&mut self, index: usize) {
+        assert!(index < 4);
+
+        // Sound, as SLVADR is only accessed by `Slave` after slave mode has
+        // been enabled, and we have a mutable reference to it.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        i2c.slvadr[index].modify(|_, w| w.sadisable().disabled());
+    }
+
+    /// Qualify the slave address at index 0
+    ///
+    /// Extends the address at index 0 to match multiple addresses, as
+    /// described by `qualifier`. The other slave addresses are not affected.
+    ///
+    /// Returns [`Error::AddressOutOfRange`], if the value in `qualifier` is
+    /// larger than 7 bits.
+    ///
+    /// [`Error::AddressOutOfRange`]: ../enum.Error.html#variant.AddressOutOfRange
+    pub fn set_qualifier(&mut self, qualifier: Qualifier) -> Result<(), Error> {
+        // Sound, as SLVQUAL0 is exclusively accessed by `Slave`, and we have a
+        // mutable reference to it.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        match qualifier {
+            Qualifier::None => {
+                i2c.slvqual0.write(|w| w.qualmode0().mask());
+            }
+            Qualifier::Mask(mask) => {
+                Error::check_address(mask)?;
+
+                i2c.slvqual0.write(|w| {
+                    w.qualmode0().mask();
+
+                    // Sound, as all possible 7-bit values are acceptable here.
+                    unsafe { w.slvqual0().bits(mask) }
+                });
+            }
+            Qualifier::Range(last) => {
+                Error::check_address(last)?;
+
+                i2c.slvqual0.write(|w| {
+                    w.qualmode0().extend();
+
+                    // Sound, as all possible 7-bit values are acceptable here.
+                    unsafe { w.slvqual0().bits(last) }
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Wait until software intervention is required
     ///
     /// The returned enum indicates the current state. Each variant provides an
@@ -103,6 +205,29 @@ where
     }
 }
 
+/// Qualifies the slave address at index 0
+///
+/// Used with [`Slave::set_qualifier`].
+///
+/// [`Slave::set_qualifier`]: struct.Slave.html#method.set_qualifier
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Qualifier {
+    /// Only the address itself is matched
+    None,
+
+    /// Ignore the address bits that are set in the mask
+    ///
+    /// A received address matches, if it is equal to address 0 in all bits
+    /// that are not set in the mask.
+    Mask(u8),
+
+    /// Match a range of addresses
+    ///
+    /// All addresses from address 0 up to and including the address provided
+    /// here match.
+    Range(u8),
+}
+
 /// The current state of the slave
 ///
 /// Each variant provides an API to react to that state. Call [`I2C::wait`] to
@@ -142,6 +267,30 @@ where
         Ok(address)
     }
 
+    /// Return the index of the slave address that was matched
+    ///
+    /// The index refers to the slave addresses that are configured using
+    /// [`Slave::set_address`].
+    ///
+    /// [`Slave::set_address`]: struct.Slave.html#method.set_address
+    pub fn index(&self) -> Result<u8, Error> {
+        Error::read::<I>()?;
+
+        // Sound, as we're only reading from the STAT register.
+        let i2c = unsafe { &*I::REGISTERS };
+
+        let index = i2c.stat.read().slvidx().bits();
+        Ok(index)
+    }
+
+    /// Indicates whether the master wants to read from the slave
+    pub fn is_read(&self) -> Result<bool, Error> {
+        Error::read::<I>()?;
+
+        let is_read = self.slvdat.read().data().bits() & 0x1 == 0x1;
+        Ok(is_read)
+    }
+
     /// Acknowledge the matched address
     pub fn ack(self) -> Result<(), Error> {
         Error::read::<I>()?;
@@ -238,6 +387,139 @@ where
     }
 }
 
+/// A register map, as exposed by many sensors and similar devices
+///
+/// Used by [`RegisterSlave`]. The methods of this trait are called from
+/// [`RegisterSlave::handle`], so if that is called from an interrupt handler,
+/// they should return quickly.
+///
+/// This trait is implemented for byte arrays, which are used as a bank of
+/// readable and writable registers.
+///
+/// [`RegisterSlave`]: struct.RegisterSlave.html
+/// [`RegisterSlave::handle`]: struct.RegisterSlave.html#method.handle
+pub trait RegisterMap {
+    /// Read the register at `register`
+    ///
+    /// Called when the master reads from the slave. The returned value is
+    /// transmitted to the master.
+    fn read(&mut self, register: u8) -> u8;
+
+    /// Write `value` to the register at `register`
+    ///
+    /// Called when the master writes to the slave. Return `false` to reject the
+    /// write, for example because the register is read-only. The received byte
+    /// is then not acknowledged.
+    fn write(&mut self, register: u8, value: u8) -> bool;
+}
+
+impl<const N: usize> RegisterMap for [u8; N] {
+    fn read(&mut self, register: u8) -> u8 {
+        self.get(register as usize).copied().unwrap_or(0xff)
+    }
+
+    fn write(&mut self, register: u8, value: u8) -> bool {
+        match self.get_mut(register as usize) {
+            Some(r) => {
+                *r = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Emulates a device with a register map
+///
+/// Implements the protocol used by many sensors and similar devices: The first
+/// byte of a write is the register pointer, each following byte is written to
+/// the register the pointer points to. Reads start at the register the pointer
+/// points to. The pointer is incremented after each byte read or written.
+///
+/// Call [`handle`] whenever the slave requires attention, typically from the
+/// interrupt handler for the "slave pending" interrupt.
+///
+/// [`handle`]: #method.handle
+#[derive(Debug)]
+pub struct RegisterSlave<M> {
+    map: M,
+    pointer: u8,
+    expect_pointer: bool,
+}
+
+impl<M> RegisterSlave<M>
+where
+    M: RegisterMap,
+{
+    /// Create a new instance of `RegisterSlave`
+    pub fn new(map: M) -> Self {
+        Self {
+            map,
+            pointer: 0,
+            expect_pointer: false,
+        }
+    }
+
+    /// Handle the current state of the slave
+    ///
+    /// Returns `nb::Error::WouldBlock`, if the slave doesn't currently require
+    /// attention. Every matched address is acknowledged.
+    pub fn handle<I, C>(
+        &mut self,
+        slave: &mut Slave<
+            I,
+            init_state::Enabled<PhantomData<C>>,
+            init_state::Enabled,
+        >,
+    ) -> nb::Result<(), Error>
+    where
+        I: Instance,
+    {
+        match slave.wait()? {
+            State::AddressMatched(state) => {
+                self.expect_pointer = true;
+                state.ack()?;
+            }
+            State::RxReady(state) => {
+                let data = state.read()?;
+
+                if self.expect_pointer {
+                    self.pointer = data;
+                    self.expect_pointer = false;
+                    state.ack()?;
+                } else if self.map.write(self.pointer, data) {
+                    self.pointer = self.pointer.wrapping_add(1);
+                    state.ack()?;
+                } else {
+                    state.nack()?;
+                }
+            }
+            State::TxReady(state) => {
+                let data = self.map.read(self.pointer);
+                self.pointer = self.pointer.wrapping_add(1);
+                state.transmit(data)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the current value of the register pointer
+    pub fn pointer(&self) -> u8 {
+        self.pointer
+    }
+
+    /// Provide access to the register map
+    pub fn map(&mut self) -> &mut M {
+        &mut self.map
+    }
+
+    /// Return the register map
+    pub fn free(self) -> M {
+        self.map
+    }
+}
+
 struct SlvCtl<I>(PhantomData<I>);
 
 // Sound, as the pointer returned is valid for the duration of the program.
@@ -111,6 +111,7 @@ pub extern crate void;
 pub(crate) mod reg_proxy;
 
 pub mod adc;
+pub mod button;
 pub mod clock;
 #[cfg(feature = "845")]
 pub mod ctimer;
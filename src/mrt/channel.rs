@@ -1,4 +1,9 @@
-use crate::reg_proxy::{Reg, RegProxy};
+use core::marker::PhantomData;
+
+use crate::{
+    pac::mrt0::channel::ctrl::MODE_A,
+    reg_proxy::{Reg, RegProxy},
+};
 
 use embedded_hal::timer::{CountDown, Periodic};
 use embedded_hal_alpha::timer::{
@@ -7,40 +12,98 @@ use embedded_hal_alpha::timer::{
 use embedded_time::{clock, fraction::Fraction, Instant};
 use void::Void;
 
-use super::{Ticks, Trait};
+use super::{mode, Ticks, Trait};
 
 /// Represents a MRT0 channel
 ///
+/// The `Mode` type parameter indicates the mode the channel is in. Channels
+/// start out in repeat mode. See [`mode`] for the available modes.
+///
 /// # `embedded-hal` traits
-/// - [`embedded_hal::timer::CountDown`]
+/// - [`embedded_hal::timer::CountDown`] (in repeat and one-shot mode)
+/// - [`embedded_hal::timer::Periodic`] (in repeat mode)
 ///
+/// [`mode`]: mode/index.html
 /// [`embedded_hal::timer::CountDown`]: #impl-CountDown
-pub struct Channel<T: Reg>(RegProxy<T>);
+/// [`embedded_hal::timer::Periodic`]: #impl-Periodic
+pub struct Channel<T: Reg, Mode = mode::Repeat>(RegProxy<T>, PhantomData<Mode>);
 
 impl<T> Channel<T>
 where
     T: Trait,
 {
     pub(super) fn new() -> Self {
-        Self(RegProxy::new())
+        Self(RegProxy::new(), PhantomData)
     }
+}
 
-    /// Start the timer
+impl<T, Mode> Channel<T, Mode>
+where
+    T: Trait,
+{
+    /// Switch the channel to repeat mode
     ///
-    /// The `reload` argument must be smaller than or equal to [`MAX_VALUE`].
+    /// In repeat mode, the timer is reloaded and keeps running, after it has
+    /// reached zero.
+    pub fn into_repeat(self) -> Channel<T, mode::Repeat> {
+        self.into_mode(MODE_A::REPEAT_INTERRUPT_MODE)
+    }
+
+    /// Switch the channel to one-shot mode
     ///
-    /// [`MAX_VALUE`]: constant.MAX_VALUE.html
-    pub fn start(&mut self, reload: Ticks) {
-        // This stops the timer, to prevent race conditions when resetting the
-        // interrupt bit
-        self.0.intval.write(|w| {
-            w.load().set_bit();
-            unsafe { w.ivalue().bits(0) }
-        });
+    /// In one-shot mode, the timer stops after it has reached zero.
+    pub fn into_one_shot(self) -> Channel<T, mode::OneShot> {
+        self.into_mode(MODE_A::ONE_SHOT_INTERRUPT_MODE)
+    }
+
+    /// Switch the channel to one-shot bus-stall mode
+    ///
+    /// In bus-stall mode, starting the timer stalls the bus until the timer
+    /// has reached zero. This provides precise short delays without any
+    /// software overhead, but also blocks all other bus masters (like DMA)
+    /// during the delay. Interrupts are not taken during the delay either.
+    ///
+    /// Use [`Channel::delay`] to use a channel in this mode.
+    ///
+    /// [`Channel::delay`]: #method.delay
+    pub fn into_one_shot_stall(self) -> Channel<T, mode::OneShotStall> {
+        self.into_mode(MODE_A::ONE_SHOT_STALL_MODE)
+    }
+
+    /// Enable the interrupt for this channel
+    ///
+    /// The interrupt is triggered whenever the timer reaches zero. All
+    /// channels share one interrupt, so the handler needs to check which
+    /// channel triggered it, either using [`Channel::is_interrupt_pending`],
+    /// or [`Status::pending_interrupts`].
+    ///
+    /// [`Channel::is_interrupt_pending`]: #method.is_interrupt_pending
+    /// [`Status::pending_interrupts`]: struct.Status.html#method.pending_interrupts
+    pub fn enable_interrupt(&mut self) {
+        self.0.ctrl.modify(|_, w| w.inten().enabled());
+    }
+
+    /// Disable the interrupt for this channel
+    pub fn disable_interrupt(&mut self) {
+        self.0.ctrl.modify(|_, w| w.inten().disabled());
+    }
+
+    /// Indicates whether the interrupt flag of this channel is set
+    ///
+    /// The flag is set whenever the timer reaches zero, regardless of whether
+    /// the interrupt is enabled.
+    pub fn is_interrupt_pending(&self) -> bool {
+        self.0.stat.read().intflag().is_pending_interrupt()
+    }
+
+    /// Clear the interrupt flag of this channel
+    pub fn clear_interrupt(&mut self) {
         self.0.stat.write(|w| w.intflag().set_bit());
-        self.0
-            .intval
-            .write(|w| unsafe { w.ivalue().bits(reload.0 + 1) });
+    }
+
+    /// Returns the index of this channel
+    pub fn index(&self) -> u8 {
+        T::INDEX
     }
 
     /// Indicates whether the timer is running
@@ -58,6 +121,65 @@ where
         self.0.intval.read().ivalue().bits()
     }
 
+    fn into_mode<NewMode>(self, mode: MODE_A) -> Channel<T, NewMode> {
+        self.stop();
+        self.0.ctrl.modify(|_, w| w.mode().variant(mode));
+        Channel(self.0, PhantomData)
+    }
+
+    fn stop(&self) {
+        // Loading zero immediately stops the timer.
+        self.0.intval.write(|w| {
+            w.load().set_bit();
+            unsafe { w.ivalue().bits(0) }
+        });
+        self.0.stat.write(|w| w.intflag().set_bit());
+    }
+}
+
+impl<T> Channel<T, mode::OneShotStall>
+where
+    T: Trait,
+{
+    /// Delay for the given number of ticks
+    ///
+    /// Stalls the bus until the timer has counted down. Returns once the
+    /// delay has passed.
+    ///
+    /// The `ticks` argument must be smaller than or equal to [`MAX_VALUE`].
+    ///
+    /// [`MAX_VALUE`]: constant.MAX_VALUE.html
+    pub fn delay(&mut self, ticks: Ticks) {
+        self.0
+            .intval
+            .write(|w| unsafe { w.ivalue().bits(ticks.0 + 1) });
+        self.0.stat.write(|w| w.intflag().set_bit());
+    }
+}
+
+impl<T, Mode> Channel<T, Mode>
+where
+    T: Trait,
+    Mode: mode::CountDown,
+{
+    /// Start the timer
+    ///
+    /// The `reload` argument must be smaller than or equal to [`MAX_VALUE`].
+    ///
+    /// [`MAX_VALUE`]: constant.MAX_VALUE.html
+    pub fn start(&mut self, reload: Ticks) {
+        // This stops the timer, to prevent race conditions when resetting the
+        // interrupt bit
+        self.0.intval.write(|w| {
+            w.load().set_bit();
+            unsafe { w.ivalue().bits(0) }
+        });
+        self.0.stat.write(|w| w.intflag().set_bit());
+        self.0
+            .intval
+            .write(|w| unsafe { w.ivalue().bits(reload.0 + 1) });
+    }
+
     /// Non-blockingly "waits" until the count down finishes
     fn wait(&mut self) -> nb::Result<(), Void> {
         if self.0.stat.read().intflag().is_pending_interrupt() {
@@ -70,9 +192,10 @@ where
     }
 }
 
-impl<T> CountDown for Channel<T>
+impl<T, Mode> CountDown for Channel<T, Mode>
 where
     T: Trait,
+    Mode: mode::CountDown,
 {
     /// The timer operates in clock ticks from the system clock, that means it
     /// runs at 12_000_000 ticks per second if you haven't changed it.
@@ -92,9 +215,10 @@ where
     }
 }
 
-impl<T> CountDownAlpha for Channel<T>
+impl<T, Mode> CountDownAlpha for Channel<T, Mode>
 where
     T: Trait,
+    Mode: mode::CountDown,
 {
     type Error = Void;
 
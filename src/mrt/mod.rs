@@ -1,22 +1,68 @@
 //! API for the MRT (Multi-Rate Timer) peripheral
 //!
-//! Please be aware that this doesn't try to abstract everything, it only
-//! implements the embedded-hal `Timer` functionality.
-//!
 //! The MRT consists of 4 channels, which are mostly separate and can each act
-//! as a run-of-the-mill timer.
+//! as a run-of-the-mill timer. Each channel can run in repeat mode, one-shot
+//! mode, or one-shot bus-stall mode (see [`mode`]).
+//!
+//! All channels share one interrupt. [`Status`] can be used to find out which
+//! channels have triggered it, and which channels are currently idle.
+//!
+//! If the `rtic-monotonic` feature is enabled, two channels can be combined
+//! into an RTIC monotonic timer (see [`monotonic`]).
+//!
+//! [`mode`]: mode/index.html
+//! [`monotonic`]: monotonic/index.html
+//! [`Status`]: struct.Status.html
+
+#[cfg(feature = "rtic-monotonic")]
+pub mod monotonic;
 
 mod channel;
 mod gen;
 mod peripheral;
+mod status;
 mod ticks;
 
 pub use self::{
     channel::Channel,
     gen::*,
     peripheral::MRT,
+    status::Status,
     ticks::{TickConversionError, Ticks},
 };
 
 /// The maximum timer value
 pub const MAX_VALUE: Ticks = Ticks(0x7fff_ffff - 1);
+
+/// Contains types that indicate the mode of a [`Channel`]
+///
+/// [`Channel`]: ../struct.Channel.html
+pub mod mode {
+    /// Repeat mode
+    ///
+    /// The timer is reloaded and keeps running, after it has reached zero.
+    pub struct Repeat;
+
+    /// One-shot mode
+    ///
+    /// The timer stops after it has reached zero.
+    pub struct OneShot;
+
+    /// One-shot bus-stall mode
+    ///
+    /// Starting the timer stalls the bus until it has reached zero.
+    pub struct OneShotStall;
+
+    /// Implemented for modes that support the `CountDown` traits
+    pub trait CountDown: private::Sealed {}
+
+    impl CountDown for Repeat {}
+    impl CountDown for OneShot {}
+
+    mod private {
+        pub trait Sealed {}
+
+        impl Sealed for super::Repeat {}
+        impl Sealed for super::OneShot {}
+    }
+}
@@ -0,0 +1,358 @@
+//! RTIC monotonic timer based on the MRT
+//!
+//! Requires the `rtic-monotonic` feature.
+//!
+//! [`Monotonic`] uses two MRT channels: One runs continuously and provides the
+//! time base. Its 31-bit counter is extended to 64 bits in software, by
+//! counting its wrap-arounds in the MRT interrupt. The other channel runs in
+//! one-shot mode and triggers the interrupt for the next scheduled task.
+//!
+//! The tick rate is fixed at 12 MHz, as RTIC requires the [`Duration`]
+//! constructors to be `const`. The system clock must run at that frequency,
+//! which [`Monotonic::new`] checks.
+//!
+//! # Example
+//!
+//! ``` ignore
+//! #[rtic::app(device = lpc8xx_hal::pac, peripherals = false)]
+//! mod app {
+//!     use lpc8xx_hal::{
+//!         mrt::{self, monotonic::Duration},
+//!         Peripherals,
+//!     };
+//!
+//!     #[monotonic(binds = MRT0, default = true)]
+//!     type Mono = mrt::monotonic::Monotonic<mrt::MRT0, mrt::MRT1>;
+//!
+//!     #[init]
+//!     fn init(_: init::Context) -> (Shared, Local, init::Monotonics) {
+//!         let p = Peripherals::take().unwrap();
+//!
+//!         let mut syscon = p.SYSCON.split();
+//!         let mrt = p.MRT0.split(&mut syscon.handle);
+//!
+//!         let mono = mrt::monotonic::Monotonic::new(
+//!             mrt.mrt0,
+//!             mrt.mrt1,
+//!             &syscon.system_clock,
+//!         );
+//!
+//!         task::spawn_after(Duration::millis(500)).unwrap();
+//!
+//!         (Shared {}, Local {}, init::Monotonics(mono))
+//!     }
+//!
+//!     // ...
+//! }
+//! ```
+//!
+//! [`Monotonic`]: struct.Monotonic.html
+//! [`Duration`]: struct.Duration.html
+//! [`Monotonic::new`]: struct.Monotonic.html#method.new
+
+use core::{
+    convert::TryFrom,
+    ops::{Add, Sub},
+};
+
+use embedded_time::duration::{Microseconds, Milliseconds, Seconds};
+
+use crate::{clock::Frequency as _, syscon};
+
+use super::{mode, Channel, Ticks, Trait, MAX_VALUE};
+
+/// The number of ticks per second
+///
+/// This is the frequency of the system clock that [`Monotonic`] requires.
+const TICKS_PER_SECOND: u64 = 12_000_000;
+
+/// The number of ticks after which the time base wraps around
+const PERIOD: u64 = MAX_VALUE.0 as u64 + 1;
+
+/// An RTIC monotonic timer based on two MRT channels
+///
+/// `T` identifies the channel that provides the time base, `C` the channel
+/// that is used to trigger the interrupt for scheduled tasks. The interrupt
+/// must be bound to `MRT0`, which is shared by all channels. The other MRT
+/// channels can still be used while the monotonic is running.
+///
+/// The system clock must run at 12 MHz, as the tick rate of [`Instant`] and
+/// [`Duration`] is fixed at that frequency. This is the default configuration,
+/// and is checked by [`Monotonic::new`].
+///
+/// Please refer to the [module documentation] for more information.
+///
+/// [`Instant`]: struct.Instant.html
+/// [`Duration`]: struct.Duration.html
+/// [`Monotonic::new`]: #method.new
+/// [module documentation]: index.html
+pub struct Monotonic<T, C>
+where
+    T: Trait,
+    C: Trait,
+{
+    time: Channel<T>,
+    compare: Channel<C, mode::OneShot>,
+    overflows: u64,
+}
+
+impl<T, C> Monotonic<T, C>
+where
+    T: Trait,
+    C: Trait,
+{
+    /// Create a new monotonic timer from two MRT channels
+    ///
+    /// The timer starts running immediately, but is reset by RTIC, before any
+    /// tasks run.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the system clock doesn't run at 12 MHz.
+    pub fn new(
+        mut time: Channel<T>,
+        compare: Channel<C>,
+        clock: &syscon::SystemClock,
+    ) -> Self {
+        assert_eq!(
+            clock.hz() as u64,
+            TICKS_PER_SECOND,
+            "Monotonic requires a system clock of 12 MHz",
+        );
+
+        time.start(MAX_VALUE);
+        time.enable_interrupt();
+
+        let mut compare = compare.into_one_shot();
+        compare.enable_interrupt();
+
+        Self {
+            time,
+            compare,
+            overflows: 0,
+        }
+    }
+
+    /// Release the MRT channels
+    pub fn free(self) -> (Channel<T>, Channel<C>) {
+        let mut time = self.time;
+        let mut compare = self.compare;
+
+        time.disable_interrupt();
+        compare.disable_interrupt();
+
+        (time, compare.into_repeat())
+    }
+
+    fn elapsed(value: u32) -> u64 {
+        PERIOD - value as u64
+    }
+}
+
+impl<T, C> rtic_monotonic::Monotonic for Monotonic<T, C>
+where
+    T: Trait,
+    C: Trait,
+{
+    // The time base needs the interrupt to count its wrap-arounds.
+    const DISABLE_INTERRUPT_ON_EMPTY_QUEUE: bool = false;
+
+    type Instant = Instant;
+    type Duration = Duration;
+
+    fn now(&mut self) -> Self::Instant {
+        // If the time base wraps around while we're reading it, the interrupt
+        // that counts the wrap-around might not have been handled yet. Reading
+        // the value before and after the interrupt flag tells us which value
+        // is consistent with the flag.
+        let before = self.time.value();
+        let wrapped = self.time.is_interrupt_pending();
+        let after = self.time.value();
+
+        let ticks = if wrapped {
+            (self.overflows + 1) * PERIOD + Self::elapsed(after)
+        } else {
+            self.overflows * PERIOD + Self::elapsed(before)
+        };
+
+        Instant::from_ticks(ticks)
+    }
+
+    fn set_compare(&mut self, instant: Self::Instant) {
+        let now = self.now();
+
+        // If the instant is too far in the future, the interrupt fires early,
+        // and RTIC sets the compare value again.
+        let ticks = (instant - now).ticks().min(MAX_VALUE.0 as u64) as u32;
+
+        self.compare.start(Ticks(ticks.max(1) - 1));
+    }
+
+    fn clear_compare_flag(&mut self) {
+        self.compare.clear_interrupt();
+    }
+
+    fn zero() -> Self::Instant {
+        Instant::from_ticks(0)
+    }
+
+    unsafe fn reset(&mut self) {
+        self.time.start(MAX_VALUE);
+        self.overflows = 0;
+    }
+
+    fn on_interrupt(&mut self) {
+        if self.time.is_interrupt_pending() {
+            self.time.clear_interrupt();
+            self.overflows += 1;
+        }
+    }
+}
+
+/// An instant in time, as measured by [`Monotonic`]
+///
+/// [`Monotonic`]: struct.Monotonic.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Instant(u64);
+
+impl Instant {
+    /// Create an instant from the number of ticks since time zero
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self(ticks)
+    }
+
+    /// Returns the number of ticks since time zero
+    pub const fn ticks(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the duration since an earlier instant
+    ///
+    /// Returns a zero duration, if `earlier` is actually later than `self`.
+    pub fn duration_since(&self, earlier: Self) -> Duration {
+        Duration(self.0.saturating_sub(earlier.0))
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.duration_since(rhs)
+    }
+}
+
+/// A duration, as measured by [`Monotonic`]
+///
+/// Can be converted from the `embedded_time` duration types.
+///
+/// ```
+/// use lpc8xx_hal::{
+///     embedded_time::duration::Milliseconds,
+///     mrt::monotonic::Duration,
+/// };
+///
+/// assert_eq!(Duration::from(Milliseconds(5u32)), Duration::micros(5_000));
+/// assert_eq!(Duration::secs(1).ticks(), 12_000_000);
+/// ```
+///
+/// [`Monotonic`]: struct.Monotonic.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Duration(u64);
+
+impl Duration {
+    /// Create a duration from a number of ticks
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self(ticks)
+    }
+
+    /// Create a duration from a number of microseconds
+    pub const fn micros(micros: u64) -> Self {
+        Self(micros * (TICKS_PER_SECOND / 1_000_000))
+    }
+
+    /// Create a duration from a number of milliseconds
+    pub const fn millis(millis: u64) -> Self {
+        Self(millis * (TICKS_PER_SECOND / 1_000))
+    }
+
+    /// Create a duration from a number of seconds
+    pub const fn secs(secs: u64) -> Self {
+        Self(secs * TICKS_PER_SECOND)
+    }
+
+    /// Returns the number of ticks
+    pub const fn ticks(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the number of whole microseconds
+    pub const fn to_micros(&self) -> u64 {
+        self.0 / (TICKS_PER_SECOND / 1_000_000)
+    }
+
+    /// Returns the number of whole milliseconds
+    pub const fn to_millis(&self) -> u64 {
+        self.0 / (TICKS_PER_SECOND / 1_000)
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl From<Microseconds> for Duration {
+    fn from(value: Microseconds) -> Self {
+        Self::micros(value.0 as u64)
+    }
+}
+
+impl From<Milliseconds> for Duration {
+    fn from(value: Milliseconds) -> Self {
+        Self::millis(value.0 as u64)
+    }
+}
+
+impl From<Seconds> for Duration {
+    fn from(value: Seconds) -> Self {
+        Self::secs(value.0 as u64)
+    }
+}
+
+impl TryFrom<Duration> for Ticks {
+    type Error = super::TickConversionError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        u32::try_from(value.0)
+            .map_err(|_| super::TickConversionError)
+            .and_then(Ticks::try_from)
+    }
+}
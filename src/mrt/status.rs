@@ -0,0 +1,59 @@
+use crate::pac::MRT0;
+
+/// Provides access to the global status of the MRT
+///
+/// Can be accessed via [`Channels`]. Since all MRT channels share one
+/// interrupt, this struct is useful in an interrupt handler that serves
+/// multiple channels.
+///
+/// [`Channels`]: struct.Channels.html
+#[derive(Debug)]
+pub struct Status(());
+
+impl Status {
+    pub(super) fn new() -> Self {
+        Self(())
+    }
+
+    /// Returns the pending interrupt flags of all channels
+    ///
+    /// Bit `n` of the returned value is set, if the interrupt flag of channel
+    /// `n` is set.
+    pub fn pending_interrupts(&self) -> u8 {
+        // Sound, as we're only reading from a register that isn't modified by
+        // reading.
+        let mrt = unsafe { &*MRT0::ptr() };
+        (mrt.irq_flag.read().bits() & 0xf) as u8
+    }
+
+    /// Clear the interrupt flags of the selected channels
+    ///
+    /// Bit `n` of `channels` selects channel `n`. Flags of channels whose bit
+    /// is not set are not affected.
+    pub fn clear_interrupts(&mut self, channels: u8) {
+        // Sound, as writing zeros to IRQ_FLAG has no effect, so this can't
+        // interfere with the interrupt flags of channels that aren't selected.
+        let mrt = unsafe { &*MRT0::ptr() };
+        mrt.irq_flag
+            .write(|w| unsafe { w.bits(channels as u32 & 0xf) });
+    }
+
+    /// Returns the index of the lowest idle channel
+    ///
+    /// Returns `None`, if all channels are running.
+    pub fn idle_channel(&self) -> Option<u8> {
+        // Sound, as we're only reading from registers that aren't modified by
+        // reading.
+        let mrt = unsafe { &*MRT0::ptr() };
+
+        let channel = mrt.idle_ch.read().chan().bits();
+
+        // IDLE_CH reads zero, if no channel is idle, which can't be
+        // distinguished from channel 0 being idle.
+        if channel == 0 && mrt.channel[0].stat.read().run().is_running() {
+            return None;
+        }
+
+        Some(channel)
+    }
+}
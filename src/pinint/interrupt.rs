@@ -1,8 +1,10 @@
 use core::marker::PhantomData;
 
+use cortex_m::interrupt;
+
 use super::traits::Trait;
 
-use crate::{init_state::Enabled, pac, pins, syscon};
+use crate::{gpio::Level, init_state::Enabled, pac, pins, syscon};
 
 /// API for controlling pin interrupts
 pub struct Interrupt<I, P, State> {
@@ -98,7 +100,12 @@ where
     }
 
     /// Fire interrupt on rising edge
+    ///
+    /// Switches the interrupt to edge-sensitive mode, if it was in
+    /// level-sensitive mode.
     pub fn enable_rising_edge(&mut self) {
+        self.select_edge_sensitive();
+
         // This is sound, as we're only doing an atomic write to a single bit
         // that no other `Interrupt` instance is writing to.
         let pint = unsafe { &*pac::PINT::ptr() };
@@ -145,7 +152,12 @@ where
     }
 
     /// Fire interrupt on falling edge
+    ///
+    /// Switches the interrupt to edge-sensitive mode, if it was in
+    /// level-sensitive mode.
     pub fn enable_falling_edge(&mut self) {
+        self.select_edge_sensitive();
+
         // This is sound, as we're only doing an atomic write to a single bit
         // that no other `Interrupt` instance is writing to.
         let pint = unsafe { &*pac::PINT::ptr() };
@@ -167,4 +179,183 @@ where
             // interrupts.
             unsafe { w.cenaf().bits(I::MASK) });
     }
+
+    /// Fire interrupt while the pin is at the given level
+    ///
+    /// Switches the interrupt to level-sensitive mode. The interrupt keeps
+    /// firing, for as long as the pin is at the `active` level. Edge detection
+    /// is disabled while the interrupt is in level-sensitive mode.
+    pub fn enable_level(&mut self, active: Level) {
+        // This is sound, as we're only doing atomic writes to a single bit
+        // that no other `Interrupt` instance is writing to, and the
+        // read-modify-write of ISEL happens within a critical section.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        Self::disable_all(pint);
+        interrupt::free(|_| {
+            pint.isel.modify(|r, w| unsafe {
+                w.pmode().bits(r.pmode().bits() | I::MASK)
+            });
+        });
+
+        // In level-sensitive mode, IENF selects the active level.
+        match active {
+            Level::High => {
+                pint.sienf.write(|w| unsafe { w.setenaf().bits(I::MASK) })
+            }
+            Level::Low => {
+                pint.cienf.write(|w| unsafe { w.cenaf().bits(I::MASK) })
+            }
+        }
+        pint.sienr.write(|w| unsafe { w.setenrl().bits(I::MASK) });
+    }
+
+    /// Don't fire interrupt on pin level
+    ///
+    /// Disables the interrupt and switches it back to edge-sensitive mode.
+    pub fn disable_level(&mut self) {
+        // Sound, for the same reasons as in `enable_level`.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        Self::disable_all(pint);
+        Self::clear_isel(pint);
+    }
+
+    /// Indicates whether the interrupt is in level-sensitive mode
+    pub fn is_level_sensitive(&self) -> bool {
+        // Sound, as we're only reading from the register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.isel.read().pmode().bits() & I::MASK != 0
+    }
+
+    /// Returns whether a rising edge has been detected, without clearing it
+    ///
+    /// Rising edges are detected regardless of whether rising edge interrupts
+    /// are enabled, or whether the interrupt is in level-sensitive mode.
+    pub fn is_rising_edge_detected(&self) -> bool {
+        // Sound, as we're only reading from the register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.rise.read().rdet().bits() & I::MASK != 0
+    }
+
+    /// Returns whether a falling edge has been detected, without clearing it
+    ///
+    /// Falling edges are detected regardless of whether falling edge
+    /// interrupts are enabled, or whether the interrupt is in level-sensitive
+    /// mode.
+    pub fn is_falling_edge_detected(&self) -> bool {
+        // Sound, as we're only reading from the register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.fall.read().fdet().bits() & I::MASK != 0
+    }
+
+    /// Returns whether the interrupt is currently being requested
+    ///
+    /// Reads the interrupt status register. In edge-sensitive mode, this is
+    /// set if an enabled edge has been detected. In level-sensitive mode, this
+    /// is set while the pin is at the active level.
+    pub fn is_pending(&self) -> bool {
+        // Sound, as we're only reading from the register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.ist.read().pstat().bits() & I::MASK != 0
+    }
+
+    /// Configure the interrupt to wake up the system
+    ///
+    /// Configures the interrupt to fire on `trigger`, and enables wake-up
+    /// from deep-sleep and power-down modes for this interrupt in the
+    /// STARTERP0 register.
+    ///
+    /// The interrupt still needs to be unmasked in the NVIC, to actually wake
+    /// up the system. See [`sleep::DeepSleep`] for how to enter deep-sleep
+    /// mode.
+    ///
+    /// [`sleep::DeepSleep`]: ../sleep/struct.DeepSleep.html
+    pub fn enable_wakeup(
+        &mut self,
+        trigger: Trigger,
+        syscon: &mut syscon::Handle,
+    ) {
+        match trigger {
+            Trigger::RisingEdge => {
+                self.disable_falling_edge();
+                self.enable_rising_edge();
+            }
+            Trigger::FallingEdge => {
+                self.disable_rising_edge();
+                self.enable_falling_edge();
+            }
+            Trigger::BothEdges => {
+                self.enable_rising_edge();
+                self.enable_falling_edge();
+            }
+            Trigger::High => self.enable_level(Level::High),
+            Trigger::Low => self.enable_level(Level::Low),
+        }
+
+        syscon.enable_pin_interrupt_wakeup(I::MASK);
+    }
+
+    /// Disable the interrupt and its wake-up from deep-sleep or power-down
+    ///
+    /// Disables the interrupt, switches it back to edge-sensitive mode, and
+    /// disables wake-up for this interrupt in the STARTERP0 register.
+    pub fn disable_wakeup(&mut self, syscon: &mut syscon::Handle) {
+        self.disable_level();
+        syscon.disable_pin_interrupt_wakeup(I::MASK);
+    }
+
+    fn select_edge_sensitive(&mut self) {
+        if !self.is_level_sensitive() {
+            return;
+        }
+
+        // Sound, for the same reasons as in `enable_level`.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        // The enable bits have a different meaning in level-sensitive mode, so
+        // they need to be reset, to not enable any edges unintentionally.
+        Self::disable_all(pint);
+        Self::clear_isel(pint);
+    }
+
+    fn disable_all(pint: &pac::pint::RegisterBlock) {
+        pint.cienr.write(|w| unsafe { w.cenrl().bits(I::MASK) });
+        pint.cienf.write(|w| unsafe { w.cenaf().bits(I::MASK) });
+    }
+
+    fn clear_isel(pint: &pac::pint::RegisterBlock) {
+        interrupt::free(|_| {
+            pint.isel.modify(|r, w| unsafe {
+                w.pmode().bits(r.pmode().bits() & !I::MASK)
+            });
+        });
+    }
+}
+
+/// The condition that fires a pin interrupt
+///
+/// Used by [`Interrupt::enable_wakeup`].
+///
+/// [`Interrupt::enable_wakeup`]: struct.Interrupt.html#method.enable_wakeup
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Trigger {
+    /// Fire on rising edges
+    RisingEdge,
+
+    /// Fire on falling edges
+    FallingEdge,
+
+    /// Fire on both rising and falling edges
+    BothEdges,
+
+    /// Fire while the pin is HIGH
+    High,
+
+    /// Fire while the pin is LOW
+    Low,
 }
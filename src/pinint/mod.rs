@@ -1,7 +1,12 @@
 //! Interface to the pin interrupts/pattern matching engine
 //!
 //! This API is currently limited. It exposes a subset of the pin interrupts
-//! functionality, and none of the pattern matching functionality.
+//! functionality. The pattern matching functionality is available through the
+//! [`pattern`] module.
+//!
+//! [`pattern`]: pattern/index.html
+
+pub mod pattern;
 
 mod gen;
 mod interrupt;
@@ -9,5 +14,8 @@ mod peripheral;
 mod traits;
 
 pub use self::{
-    gen::*, interrupt::Interrupt, peripheral::PININT, traits::Trait,
+    gen::*,
+    interrupt::{Interrupt, Trigger},
+    peripheral::PININT,
+    traits::Trait,
 };
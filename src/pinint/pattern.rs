@@ -0,0 +1,374 @@
+//! Pattern match engine
+//!
+//! The pattern match engine evaluates a boolean expression on up to eight
+//! pin interrupt inputs, and fires a pin interrupt, whenever the expression
+//! matches. The expression is a sum (OR) of products (AND). Each factor of a
+//! product is a [`Condition`] on one of the inputs, and takes up one of eight
+//! bit slices in the engine.
+//!
+//! A [`Pattern`] is built from [`Term`]s, which represent the products. The
+//! pin interrupt that fires when a term matches has the same index as the last
+//! slice of that term. [`Pattern::endpoints`] returns these interrupts. The
+//! pattern is installed using [`PatternMatch::enable`].
+//!
+//! # Example
+//!
+//! Wake up, if the door is open while the ignition is off, or if the alarm
+//! button is pressed:
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     pinint::pattern::{Condition, Pattern, Term},
+//!     Peripherals,
+//! };
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let gpio = p.GPIO.enable(&mut syscon.handle);
+//! let mut pinint = p.PININT.enable(&mut syscon.handle);
+//!
+//! let door = p.pins.pio0_4.into_input_pin(gpio.tokens.pio0_4);
+//! let ignition = p.pins.pio0_12.into_input_pin(gpio.tokens.pio0_12);
+//! let alarm = p.pins.pio0_13.into_input_pin(gpio.tokens.pio0_13);
+//!
+//! let door = pinint
+//!     .interrupts
+//!     .pinint0
+//!     .select(door.inner(), &mut syscon.handle);
+//! let ignition = pinint
+//!     .interrupts
+//!     .pinint1
+//!     .select(ignition.inner(), &mut syscon.handle);
+//! let alarm = pinint
+//!     .interrupts
+//!     .pinint2
+//!     .select(alarm.inner(), &mut syscon.handle);
+//!
+//! let pattern = Pattern::new()
+//!     .term(
+//!         Term::new()
+//!             .with(&door, Condition::High)
+//!             .with(&ignition, Condition::Low),
+//!     )
+//!     .term(Term::new().with(&alarm, Condition::StickyFallingEdge));
+//!
+//! // The first term ends in slice 1, the second in slice 2. This means that
+//! // the terms fire pin interrupts 1 and 2 respectively.
+//! assert_eq!(pattern.endpoints(), 0b0000_0110);
+//!
+//! pinint.pattern_match.enable(&pattern, false).unwrap();
+//! ```
+//!
+//! [`Condition`]: enum.Condition.html
+//! [`Pattern`]: struct.Pattern.html
+//! [`Term`]: struct.Term.html
+//! [`Pattern::endpoints`]: struct.Pattern.html#method.endpoints
+//! [`PatternMatch::enable`]: struct.PatternMatch.html#method.enable
+
+use core::marker::PhantomData;
+
+use crate::{init_state::Enabled, pac, pins};
+
+use super::{traits::Trait, Interrupt};
+
+/// The number of bit slices in the pattern match engine
+pub const SLICES: usize = 8;
+
+/// API for controlling the pattern match engine
+///
+/// Available through the `pattern_match` field of [`PININT`].
+///
+/// [`PININT`]: ../struct.PININT.html
+pub struct PatternMatch<State> {
+    _state: PhantomData<State>,
+}
+
+impl<State> PatternMatch<State> {
+    pub(super) fn new() -> Self {
+        Self {
+            _state: PhantomData,
+        }
+    }
+}
+
+impl PatternMatch<Enabled> {
+    /// Configure and enable the pattern match engine
+    ///
+    /// Switches the pin interrupts from the regular pin interrupt mode to
+    /// pattern match mode. The pin interrupts that are fired by the pattern
+    /// are returned by [`Pattern::endpoints`]. All other pin interrupts don't
+    /// fire, while pattern match mode is enabled.
+    ///
+    /// If `rxev` is `true`, the RXEV output to the CPU is driven, whenever the
+    /// pattern matches. This can be used to wake up the CPU from a `WFE`.
+    ///
+    /// Configuring the pattern resets the state of all sticky conditions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the pattern is empty, or if it uses more than the
+    /// available slices. The configuration is left unchanged in that case.
+    ///
+    /// [`Pattern::endpoints`]: struct.Pattern.html#method.endpoints
+    pub fn enable(
+        &mut self,
+        pattern: &Pattern,
+        rxev: bool,
+    ) -> Result<(), Error> {
+        let (pmsrc, pmcfg) = pattern.registers()?;
+
+        // Sound, as the pattern match registers are exclusively accessed by
+        // this instance, and the values have been computed to be valid by
+        // `Pattern`.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.pmsrc.write(|w| unsafe { w.bits(pmsrc) });
+        pint.pmcfg.write(|w| unsafe { w.bits(pmcfg) });
+        pint.pmctrl
+            .write(|w| w.sel_pmatch().pattern_match().ena_rxev().bit(rxev));
+
+        Ok(())
+    }
+
+    /// Disable the pattern match engine
+    ///
+    /// Switches the pin interrupts back to the regular pin interrupt mode.
+    pub fn disable(&mut self) {
+        // Sound, as the pattern match registers are exclusively accessed by
+        // this instance.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.pmctrl
+            .write(|w| w.sel_pmatch().pin_interrupt().ena_rxev().disabled());
+    }
+
+    /// Returns the current state of the pattern match terms
+    ///
+    /// Bit `n` of the returned value is set, if the term that ends in slice
+    /// `n` currently matches.
+    pub fn state(&self) -> u8 {
+        // Sound, as we're only reading from the register.
+        let pint = unsafe { &*pac::PINT::ptr() };
+
+        pint.pmctrl.read().pmat().bits()
+    }
+}
+
+/// A condition on a single input of the pattern match engine
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Condition {
+    /// The input is HIGH
+    High,
+
+    /// The input is LOW
+    Low,
+
+    /// A rising edge has occurred since the pattern was last configured
+    StickyRisingEdge,
+
+    /// A falling edge has occurred since the pattern was last configured
+    StickyFallingEdge,
+
+    /// Any edge has occurred since the pattern was last configured
+    StickyEdge,
+
+    /// An edge is occurring right now
+    ///
+    /// Unlike the sticky conditions, this only matches for one clock cycle of
+    /// the pattern match engine.
+    Edge,
+}
+
+impl Condition {
+    /// The value of the CFG field in PMCFG for this condition
+    fn bits(self) -> u32 {
+        match self {
+            Condition::StickyRisingEdge => 0x1,
+            Condition::StickyFallingEdge => 0x2,
+            Condition::StickyEdge => 0x3,
+            Condition::High => 0x4,
+            Condition::Low => 0x5,
+            Condition::Edge => 0x7,
+        }
+    }
+}
+
+/// A product term of a [`Pattern`]
+///
+/// Matches, if all of its conditions are met.
+///
+/// [`Pattern`]: struct.Pattern.html
+#[derive(Clone, Copy, Debug)]
+pub struct Term {
+    slices: Slices,
+}
+
+impl Term {
+    /// Create an empty term
+    pub fn new() -> Self {
+        Self {
+            slices: Slices::new(),
+        }
+    }
+
+    /// Add a condition on a pin interrupt input to the term
+    ///
+    /// The same input can be used in multiple conditions, both within a term,
+    /// and across terms.
+    pub fn with<I, P>(
+        mut self,
+        _: &Interrupt<I, P, Enabled>,
+        condition: Condition,
+    ) -> Self
+    where
+        I: Trait,
+        P: pins::Trait,
+    {
+        self.slices.push(I::INDEX as u8, condition);
+        self
+    }
+}
+
+impl Default for Term {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A sum-of-products expression for the pattern match engine
+///
+/// Matches, if any of its [`Term`]s match. Please refer to the
+/// [module documentation] for more information.
+///
+/// [`Term`]: struct.Term.html
+/// [module documentation]: index.html
+#[derive(Clone, Copy, Debug)]
+pub struct Pattern {
+    slices: Slices,
+    endpoints: u8,
+}
+
+impl Pattern {
+    /// Create an empty pattern
+    pub fn new() -> Self {
+        Self {
+            slices: Slices::new(),
+            endpoints: 0,
+        }
+    }
+
+    /// Add a term to the pattern
+    ///
+    /// Empty terms are ignored.
+    pub fn term(mut self, term: Term) -> Self {
+        if term.slices.len == 0 && !term.slices.overflow {
+            return self;
+        }
+
+        for &(input, condition) in term.slices.used() {
+            self.slices.push(input, condition);
+        }
+        self.slices.overflow |= term.slices.overflow;
+
+        if !self.slices.overflow {
+            self.endpoints |= 0x1 << (self.slices.len - 1);
+        }
+
+        self
+    }
+
+    /// Returns the pin interrupts that are fired by the terms of this pattern
+    ///
+    /// Bit `n` of the returned value is set, if a term ends in slice `n`, and
+    /// therefore fires pin interrupt `n`, when it matches.
+    pub fn endpoints(&self) -> u8 {
+        self.endpoints
+    }
+
+    /// Returns the number of slices used by this pattern
+    pub fn slices(&self) -> usize {
+        self.slices.len
+    }
+
+    /// Compute the values of the PMSRC and PMCFG registers
+    pub(super) fn registers(&self) -> Result<(u32, u32), Error> {
+        if self.slices.overflow {
+            return Err(Error::TooManySlices);
+        }
+        if self.slices.len == 0 {
+            return Err(Error::Empty);
+        }
+
+        let mut pmsrc = 0;
+        let mut pmcfg = 0;
+
+        for (i, &(input, condition)) in self.slices.slices.iter().enumerate() {
+            let shift = 8 + 3 * i;
+
+            pmsrc |= (input as u32) << shift;
+
+            // Unused slices are set to constant LOW. They all belong to the
+            // term that ends in the last slice, which can therefore never
+            // match.
+            let cfg = if i < self.slices.len {
+                condition.bits()
+            } else {
+                0x6
+            };
+            pmcfg |= cfg << shift;
+        }
+
+        // The last slice is always an endpoint, and has no bit in PROD_ENDPTS.
+        pmcfg |= (self.endpoints & 0x7f) as u32;
+
+        Ok((pmsrc, pmcfg))
+    }
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Indicates that a pattern could not be configured
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The pattern uses more than the eight available slices
+    TooManySlices,
+
+    /// The pattern has no terms
+    Empty,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Slices {
+    slices: [(u8, Condition); SLICES],
+    len: usize,
+    overflow: bool,
+}
+
+impl Slices {
+    fn new() -> Self {
+        Self {
+            slices: [(0, Condition::High); SLICES],
+            len: 0,
+            overflow: false,
+        }
+    }
+
+    fn push(&mut self, input: u8, condition: Condition) {
+        if self.len == SLICES {
+            self.overflow = true;
+            return;
+        }
+
+        self.slices[self.len] = (input, condition);
+        self.len += 1;
+    }
+
+    fn used(&self) -> &[(u8, Condition)] {
+        &self.slices[..self.len]
+    }
+}
@@ -44,6 +44,9 @@ macro_rules! pins {
         }
 
 
+        /// The port and id of every pin in [`Pins`]
+        pub(crate) const ALL: &[(u8, u8)] = &[$(($port, $id),)*];
+
         $(
             /// Identifies a specific pin
             ///
@@ -54,6 +57,13 @@ macro_rules! pins {
             #[allow(non_camel_case_types)]
             pub struct $type(());
 
+            impl $type {
+                /// The number that identifies this pin in the SWM registers
+                // Only used for pins that have fixed functions.
+                #[allow(dead_code)]
+                pub(crate) const NUMBER: u8 = $id | $port << 5;
+            }
+
             impl Trait for $type {
                 fn port(&self) -> u8 {
                     $port
@@ -422,6 +422,48 @@ where
         }
     }
 
+    /// Transitions this pin into the dynamic SWM state
+    ///
+    /// This method is only available while the pin is in the unused state. Code
+    /// that attempts to call this method while the pin is in any other state
+    /// will not compile. See [State Management] for more information on
+    /// managing pin states.
+    ///
+    /// Consumes this pin instance and returns a [`GenericPin`] in the
+    /// [`SwmDynamic`] state. Movable functions can be assigned to it using
+    /// [`Function::assign_dynamic`], which checks for conflicts at runtime.
+    /// This is useful, if the pin configuration is only known at runtime, for
+    /// example because it's read from external memory. Otherwise, the typed
+    /// SWM API, which starts with [`into_swm_pin`], should be preferred.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use lpc8xx_hal::{pins, Peripherals};
+    ///
+    /// let p = Peripherals::take().unwrap();
+    ///
+    /// // Gather pins in an array
+    /// let my_pins: [pins::Pin<pins::GenericPin, pins::state::SwmDynamic>; 2] = [
+    ///     p.pins.pio0_12.into_dynamic_swm_pin(),
+    ///     p.pins.pio0_13.into_dynamic_swm_pin(),
+    /// ];
+    ///
+    /// // The pins are now ready for runtime function assignment
+    /// ```
+    ///
+    /// [State Management]: #state-management
+    /// [`GenericPin`]: struct.GenericPin.html
+    /// [`SwmDynamic`]: state/struct.SwmDynamic.html
+    /// [`Function::assign_dynamic`]: ../swm/struct.Function.html#method.assign_dynamic
+    /// [`into_swm_pin`]: #method.into_swm_pin
+    pub fn into_dynamic_swm_pin(self) -> Pin<GenericPin, state::SwmDynamic> {
+        Pin {
+            ty: GenericPin::new(self.ty.port(), self.ty.id()),
+            _state: state::SwmDynamic,
+        }
+    }
+
     /// Transition pin into a Dynamic Generic Pin, i.e.
     /// - GPIO direction switchable at runtime
     /// - Pin identifying information is not part of the Pin's type, e.g. can be generalized and
@@ -67,3 +67,15 @@ impl<Output, Inputs> Swm<Output, Inputs> {
 }
 
 impl<Output, Inputs> State for Swm<Output, Inputs> {}
+
+/// Marks a [`Pin`] as being available for runtime switch matrix assignment
+///
+/// Unlike [`Swm`], this state doesn't track the assigned functions in the
+/// type system. The dynamic SWM API checks the switch matrix registers at
+/// runtime instead.
+///
+/// [`Pin`]: ../struct.Pin.html
+/// [`Swm`]: struct.Swm.html
+pub struct SwmDynamic;
+
+impl State for SwmDynamic {}
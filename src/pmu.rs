@@ -31,7 +31,10 @@
 
 use cortex_m::{asm, interrupt};
 
-use crate::{clock, init_state, pac};
+use crate::{
+    clock, init_state, pac,
+    pins::{self, Pin},
+};
 
 /// Entry point to the PMU API
 ///
@@ -67,6 +70,7 @@ impl PMU {
         Parts {
             handle: Handle { pmu: self.pmu },
             low_power_clock: LowPowerClock::new(),
+            wkt_clock_input: WktClockInput::new(),
         }
     }
 
@@ -99,6 +103,9 @@ pub struct Parts {
 
     /// The 10 kHz low-power clock
     pub low_power_clock: LowPowerClock<init_state::Disabled>,
+
+    /// The external clock input of the self-wake-up timer (WKTCLKIN)
+    pub wkt_clock_input: WktClockInput<init_state::Disabled>,
 }
 
 /// Handle to the PMU peripheral
@@ -285,3 +292,96 @@ impl<State> clock::Frequency for LowPowerClock<State> {
 }
 
 impl clock::Enabled for LowPowerClock<init_state::Enabled> {}
+
+/// The external clock input of the self-wake-up timer (WKTCLKIN)
+///
+/// This is one of the clocks that can be used to run the self-wake-up timer
+/// (WKT). The clock signal is supplied on pin PIO0_28. See user manual, section
+/// 18.5.1.
+pub struct WktClockInput<State = init_state::Enabled> {
+    pin: Option<Pin<pins::PIO0_28, pins::state::Unused>>,
+    hz: u32,
+    _state: State,
+}
+
+impl WktClockInput<init_state::Disabled> {
+    pub(crate) fn new() -> Self {
+        WktClockInput {
+            pin: None,
+            hz: 0,
+            _state: init_state::Disabled,
+        }
+    }
+
+    /// Enable the external clock input
+    ///
+    /// This method is only available, if `WktClockInput` is in the
+    /// [`Disabled`] state. Code that attempts to call this method when the
+    /// clock is already enabled will not compile.
+    ///
+    /// Consumes pin PIO0_28, which receives the clock signal, and the
+    /// frequency of that signal in Hz, as the HAL has no way of knowing it.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `hz` is zero.
+    ///
+    /// [`Disabled`]: ../init_state/struct.Disabled.html
+    pub fn enable(
+        self,
+        pin: Pin<pins::PIO0_28, pins::state::Unused>,
+        hz: u32,
+        pmu: &mut Handle,
+    ) -> WktClockInput<init_state::Enabled> {
+        assert!(hz > 0);
+
+        pmu.pmu
+            .dpdctrl
+            .modify(|_, w| w.wakeclkpad_disable().enabled());
+
+        WktClockInput {
+            pin: Some(pin),
+            hz,
+            _state: init_state::Enabled(()),
+        }
+    }
+}
+
+impl WktClockInput<init_state::Enabled> {
+    /// Disable the external clock input
+    ///
+    /// This method is only available, if `WktClockInput` is in the
+    /// [`Enabled`] state. Code that attempts to call this method when the clock
+    /// is already disabled will not compile.
+    ///
+    /// Returns the disabled clock input, as well as pin PIO0_28.
+    ///
+    /// [`Enabled`]: ../init_state/struct.Enabled.html
+    pub fn disable(
+        self,
+        pmu: &mut Handle,
+    ) -> (
+        WktClockInput<init_state::Disabled>,
+        Pin<pins::PIO0_28, pins::state::Unused>,
+    ) {
+        pmu.pmu
+            .dpdctrl
+            .modify(|_, w| w.wakeclkpad_disable().disabled());
+
+        let pin = match self.pin {
+            Some(pin) => pin,
+            // can't happen, as the pin is always set in the enabled state
+            None => unreachable!(),
+        };
+
+        (WktClockInput::new(), pin)
+    }
+}
+
+impl clock::Frequency for WktClockInput<init_state::Enabled> {
+    fn hz(&self) -> u32 {
+        self.hz
+    }
+}
+
+impl clock::Enabled for WktClockInput<init_state::Enabled> {}
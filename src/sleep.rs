@@ -3,11 +3,15 @@
 //! This module provides a higher-level API layer that can be used to put the
 //! microcontroller to sleep for a given amount of time.
 //!
-//! Both sleeping via busy waiting and via regular sleep mode are supported.
-//! Please refer to [`sleep::Busy`] and [`sleep::Regular`] for more details.
+//! Sleeping via busy waiting, regular sleep mode, deep-sleep mode, and
+//! power-down mode is supported. Please refer to [`sleep::Busy`],
+//! [`sleep::Regular`], [`sleep::DeepSleep`], and [`sleep::PowerDown`] for
+//! more details.
 //!
 //! [`sleep::Busy`]: struct.Busy.html
 //! [`sleep::Regular`]: struct.Regular.html
+//! [`sleep::DeepSleep`]: struct.DeepSleep.html
+//! [`sleep::PowerDown`]: struct.PowerDown.html
 
 use cortex_m::{asm, interrupt};
 use embedded_hal::prelude::*;
@@ -17,6 +21,7 @@ use crate::{
     clock::{self, Ticks},
     pac::{self, Interrupt, NVIC},
     pmu,
+    syscon::{self, WktWakeup},
     wkt::{self, WKT},
 };
 
@@ -210,3 +215,289 @@ where
         });
     }
 }
+
+/// Deep-sleep mode
+///
+/// Provides a [`Sleep`] implementation for deep-sleep mode and uses the [WKT]
+/// to wake the microcontroller up again, at the right time. Only clocks that
+/// keep running in deep-sleep mode can be used. See [`wkt::LowPowerCapable`]
+/// for more details.
+///
+/// Before entering deep-sleep mode, the IRC/FRO is selected as the main clock,
+/// as required by the user manual, and PDAWAKECFG is configured to restore
+/// the current power configuration on wake-up. The previous main clock
+/// selection is restored after waking up. The WKT wake-up interrupt is enabled
+/// in STARTERP1, and stays enabled.
+///
+/// If the main clock runs from the system PLL, the PLL is powered down during
+/// deep-sleep. After waking up, the IRC/FRO stays selected until the PLL has
+/// locked again, so the wake-up latency includes the PLL lock time.
+///
+/// # Limitations
+///
+/// The IRC/FRO must be powered, when going to sleep.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     Peripherals,
+///     pac::CorePeripherals,
+///     sleep,
+/// };
+///
+/// let mut cp = CorePeripherals::take().unwrap();
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut pmu    = p.PMU.split();
+/// let mut syscon = p.SYSCON.split();
+/// let mut wkt    = p.WKT.enable(&mut syscon.handle);
+///
+/// let clock = pmu.low_power_clock.enable(&mut pmu.handle);
+///
+/// let mut sleep = sleep::DeepSleep::prepare(
+///     &mut pmu.handle,
+///     &mut cp.SCB,
+///     &mut syscon.handle,
+///     &mut wkt,
+/// );
+///
+/// // This will put the microcontroller into deep-sleep mode.
+/// sleep.sleep_ms(&clock, 1000);
+/// ```
+///
+/// [`wkt::LowPowerCapable`]: ../wkt/trait.LowPowerCapable.html
+pub struct DeepSleep<'r> {
+    pmu: &'r mut pmu::Handle,
+    scb: &'r mut pac::SCB,
+    syscon: &'r mut syscon::Handle,
+    wkt: &'r mut WKT,
+}
+
+impl<'r> DeepSleep<'r> {
+    /// Prepare deep-sleep mode
+    ///
+    /// Returns an instance of `sleep::DeepSleep`, which implements [`Sleep`]
+    /// and can therefore be used to put the microcontroller to sleep.
+    ///
+    /// Requires references to various peripherals, which will be borrowed for
+    /// as long as the `sleep::DeepSleep` instance exists, as they will be
+    /// needed for every call to [`Sleep::sleep`].
+    pub fn prepare(
+        pmu: &'r mut pmu::Handle,
+        scb: &'r mut pac::SCB,
+        syscon: &'r mut syscon::Handle,
+        wkt: &'r mut WKT,
+    ) -> Self {
+        DeepSleep {
+            pmu,
+            scb,
+            syscon,
+            wkt,
+        }
+    }
+
+    /// Puts the processor to sleep for the given number of milliseconds
+    ///
+    /// Uses the frequency of `clock` to compute the number of ticks. Sleep
+    /// times that exceed the range of the WKT counter are supported.
+    pub fn sleep_ms<Clock>(&mut self, clock: &Clock, ms: u32)
+    where
+        Clock: clock::Enabled + clock::Frequency + wkt::LowPowerCapable,
+    {
+        self.wkt.use_clock(clock);
+        sleep_low_power(
+            self.pmu,
+            self.scb,
+            self.syscon,
+            self.wkt,
+            ms_to_count(clock, ms),
+            pmu::Handle::enter_deep_sleep_mode,
+        );
+    }
+}
+
+impl<'r, Clock> Sleep<Clock> for DeepSleep<'r>
+where
+    Clock: clock::Enabled + wkt::LowPowerCapable,
+{
+    fn sleep<'clock, T>(&mut self, ticks: T)
+    where
+        Clock: 'clock,
+        T: Into<Ticks<'clock, Clock>>,
+    {
+        let ticks: Ticks<Clock> = ticks.into();
+
+        self.wkt.select_clock::<Clock>();
+        sleep_low_power(
+            self.pmu,
+            self.scb,
+            self.syscon,
+            self.wkt,
+            ticks.value as u64,
+            pmu::Handle::enter_deep_sleep_mode,
+        );
+    }
+}
+
+/// Power-down mode
+///
+/// Provides a [`Sleep`] implementation for power-down mode and uses the [WKT]
+/// to wake the microcontroller up again, at the right time. Works exactly like
+/// [`sleep::DeepSleep`], except that power-down mode is used, which saves more
+/// energy, but takes longer to wake up from. As with [`sleep::DeepSleep`], the
+/// wake-up latency includes the PLL lock time, if the main clock runs from the
+/// system PLL.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use lpc8xx_hal::{
+///     prelude::*,
+///     Peripherals,
+///     clock::Ticks,
+///     pac::CorePeripherals,
+///     sleep,
+/// };
+///
+/// let mut cp = CorePeripherals::take().unwrap();
+/// let mut p = Peripherals::take().unwrap();
+///
+/// let mut pmu    = p.PMU.split();
+/// let mut syscon = p.SYSCON.split();
+/// let mut wkt    = p.WKT.enable(&mut syscon.handle);
+///
+/// let clock = pmu.low_power_clock.enable(&mut pmu.handle);
+///
+/// let mut sleep = sleep::PowerDown::prepare(
+///     &mut pmu.handle,
+///     &mut cp.SCB,
+///     &mut syscon.handle,
+///     &mut wkt,
+/// );
+///
+/// let delay = Ticks { value: 10_000, clock: &clock }; // 1000 ms
+///
+/// // This will put the microcontroller into power-down mode.
+/// sleep.sleep(delay);
+/// ```
+///
+/// [`sleep::DeepSleep`]: struct.DeepSleep.html
+pub struct PowerDown<'r> {
+    pmu: &'r mut pmu::Handle,
+    scb: &'r mut pac::SCB,
+    syscon: &'r mut syscon::Handle,
+    wkt: &'r mut WKT,
+}
+
+impl<'r> PowerDown<'r> {
+    /// Prepare power-down mode
+    ///
+    /// Returns an instance of `sleep::PowerDown`, which implements [`Sleep`]
+    /// and can therefore be used to put the microcontroller to sleep.
+    ///
+    /// Requires references to various peripherals, which will be borrowed for
+    /// as long as the `sleep::PowerDown` instance exists, as they will be
+    /// needed for every call to [`Sleep::sleep`].
+    pub fn prepare(
+        pmu: &'r mut pmu::Handle,
+        scb: &'r mut pac::SCB,
+        syscon: &'r mut syscon::Handle,
+        wkt: &'r mut WKT,
+    ) -> Self {
+        PowerDown {
+            pmu,
+            scb,
+            syscon,
+            wkt,
+        }
+    }
+
+    /// Puts the processor to sleep for the given number of milliseconds
+    ///
+    /// Uses the frequency of `clock` to compute the number of ticks. Sleep
+    /// times that exceed the range of the WKT counter are supported.
+    pub fn sleep_ms<Clock>(&mut self, clock: &Clock, ms: u32)
+    where
+        Clock: clock::Enabled + clock::Frequency + wkt::LowPowerCapable,
+    {
+        self.wkt.use_clock(clock);
+        sleep_low_power(
+            self.pmu,
+            self.scb,
+            self.syscon,
+            self.wkt,
+            ms_to_count(clock, ms),
+            pmu::Handle::enter_power_down_mode,
+        );
+    }
+}
+
+impl<'r, Clock> Sleep<Clock> for PowerDown<'r>
+where
+    Clock: clock::Enabled + wkt::LowPowerCapable,
+{
+    fn sleep<'clock, T>(&mut self, ticks: T)
+    where
+        Clock: 'clock,
+        T: Into<Ticks<'clock, Clock>>,
+    {
+        let ticks: Ticks<Clock> = ticks.into();
+
+        self.wkt.select_clock::<Clock>();
+        sleep_low_power(
+            self.pmu,
+            self.scb,
+            self.syscon,
+            self.wkt,
+            ticks.value as u64,
+            pmu::Handle::enter_power_down_mode,
+        );
+    }
+}
+
+fn ms_to_count<Clock>(clock: &Clock, ms: u32) -> u64
+where
+    Clock: clock::Frequency,
+{
+    ms as u64 * clock.hz() as u64 / 1000
+}
+
+fn sleep_low_power(
+    pmu: &mut pmu::Handle,
+    scb: &mut pac::SCB,
+    syscon: &mut syscon::Handle,
+    wkt: &mut WKT,
+    count: u64,
+    enter: unsafe fn(&mut pmu::Handle, &mut pac::SCB),
+) {
+    // If we try to sleep for zero cycles, we'll never wake up again.
+    if count == 0 {
+        return;
+    }
+
+    // Without this, the WKT interrupt won't wake up the system.
+    syscon.enable_interrupt_wakeup::<WktWakeup>();
+
+    let main_clock = syscon.prepare_deep_sleep();
+    wkt.start_count(count);
+
+    // See `Regular::sleep` for an explanation of why this works without an
+    // interrupt handler.
+    interrupt::free(|_| {
+        // Safe, because this is not going to interfere with the critical
+        // section.
+        unsafe { NVIC::unmask(Interrupt::WKT) };
+
+        while let Err(nb::Error::WouldBlock) = wkt.wait() {
+            // Sound, as `prepare_deep_sleep` has configured PDAWAKECFG to
+            // match the current power configuration.
+            unsafe { enter(pmu, scb) };
+        }
+
+        NVIC::mask(Interrupt::WKT);
+    });
+
+    syscon.restore_main_clock(main_clock);
+}
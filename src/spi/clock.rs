@@ -1,11 +1,15 @@
 use core::marker::PhantomData;
 
-use crate::syscon::{self, clock_source::PeripheralClockSelector};
+use crate::{
+    clock::{self, Frequency},
+    syscon::{self, clock_source::PeripheralClockSelector},
+};
 
 /// Contains the clock configuration for an SPI instance
 pub struct Clock<Clock> {
     pub(crate) divval: u16,
-    // The fields in the DLY register are ignored, since SSEL & EOF aren't used
+    // The fields in the DLY register are configured per device. See
+    // `DeviceSettings`.
     pub(crate) _clock: PhantomData<Clock>,
 }
 
@@ -22,6 +26,136 @@ where
     }
 }
 
+#[cfg(feature = "82x")]
+impl Clock<()> {
+    /// Create the clock config for a given SCK frequency
+    ///
+    /// SPI is always powered by the system clock on LPC82x. Computes the
+    /// divider from the frequency of the system clock. See
+    /// [`Divider::calculate`] for details.
+    ///
+    /// [`Divider::calculate`]: struct.Divider.html#method.calculate
+    pub fn from_frequency(
+        clock: &syscon::SystemClock,
+        sck_hz: u32,
+    ) -> Result<Self, ClockError> {
+        let divider = Divider::calculate(clock.hz(), sck_hz)?;
+        Ok(Self::new(&(), divider.divval))
+    }
+}
+
+#[cfg(feature = "845")]
+impl<C> Clock<C>
+where
+    C: ClockSource + Frequency,
+{
+    /// Create the clock config for a given SCK frequency
+    ///
+    /// Computes the divider from the frequency of the clock source. See
+    /// [`Divider::calculate`] for details.
+    ///
+    /// [`Divider::calculate`]: struct.Divider.html#method.calculate
+    pub fn from_frequency(clock: &C, sck_hz: u32) -> Result<Self, ClockError> {
+        let divider = Divider::calculate(clock.hz(), sck_hz)?;
+        Ok(Self::new(clock, divider.divval))
+    }
+}
+
+/// Divider configuration for an SPI instance
+///
+/// This is computed by [`Divider::calculate`], independently of any hardware.
+/// Pass `divval` to [`Clock::new`] to use it.
+///
+/// [`Divider::calculate`]: #method.calculate
+/// [`Clock::new`]: struct.Clock.html#method.new
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divider {
+    /// The value of the DIVVAL field
+    ///
+    /// The clock source is divided by `divval + 1` to get the SCK frequency.
+    pub divval: u16,
+
+    /// The resulting SCK frequency in Hz
+    pub frequency: u32,
+
+    /// The deviation of the resulting frequency from the requested one
+    ///
+    /// Specified in parts per million. Since the frequency never exceeds the
+    /// requested one, this is never positive.
+    pub error_ppm: i32,
+}
+
+impl Divider {
+    /// Compute the divider for a given SCK frequency
+    ///
+    /// `clock_hz` is the frequency of the SPI clock source, `sck_hz` is the
+    /// requested frequency of SCK. Picks the smallest divider that doesn't
+    /// exceed `sck_hz`, so the SPI never runs faster than connected devices
+    /// allow.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClockError::FrequencyOutOfRange`], if `sck_hz` is 0. Returns
+    /// [`ClockError::ClockTooFast`], if the clock source can't be divided down
+    /// far enough.
+    ///
+    /// # Examples
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::spi::{ClockError, Divider};
+    ///
+    /// let divider = Divider::calculate(12_000_000, 1_000_000).unwrap();
+    /// assert_eq!(divider.divval, 11);
+    /// assert_eq!(divider.frequency, 1_000_000);
+    /// assert_eq!(divider.error_ppm, 0);
+    ///
+    /// let divider = Divider::calculate(12_000_000, 5_000_000).unwrap();
+    /// assert_eq!(divider.frequency, 4_000_000);
+    /// assert_eq!(divider.error_ppm, -200_000);
+    ///
+    /// assert_eq!(
+    ///     Divider::calculate(12_000_000, 100),
+    ///     Err(ClockError::ClockTooFast),
+    /// );
+    /// ```
+    ///
+    /// [`ClockError::FrequencyOutOfRange`]: enum.ClockError.html#variant.FrequencyOutOfRange
+    /// [`ClockError::ClockTooFast`]: enum.ClockError.html#variant.ClockTooFast
+    pub fn calculate(clock_hz: u32, sck_hz: u32) -> Result<Self, ClockError> {
+        if sck_hz == 0 {
+            return Err(ClockError::FrequencyOutOfRange);
+        }
+
+        let div = clock_hz.div_ceil(sck_hz).max(1);
+        if div > 0x1_0000 {
+            return Err(ClockError::ClockTooFast);
+        }
+
+        let frequency = clock_hz / div;
+
+        Ok(Self {
+            divval: (div - 1) as u16,
+            frequency,
+            error_ppm: clock::error_ppm(frequency, sck_hz),
+        })
+    }
+}
+
+/// Error computing an SPI clock configuration
+///
+/// Returned by [`Divider::calculate`] and [`Clock::from_frequency`].
+///
+/// [`Divider::calculate`]: struct.Divider.html#method.calculate
+/// [`Clock::from_frequency`]: struct.Clock.html#method.from_frequency
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockError {
+    /// The requested frequency is 0
+    FrequencyOutOfRange,
+
+    /// The clock source is too fast to be divided down to the frequency
+    ClockTooFast,
+}
+
 /// Implemented for SPI clock sources
 pub trait ClockSource: private::Sealed {
     /// Select the clock source
@@ -0,0 +1,434 @@
+use embedded_hal::spi::{Mode, Phase, Polarity, MODE_0};
+use embedded_hal_alpha::spi::{
+    blocking::{
+        Operation, Read, Transactional, Transfer, TransferInplace, Write,
+        WriteIter,
+    },
+    ErrorKind,
+};
+
+use crate::{init_state::Enabled, pac::spi0::RegisterBlock};
+
+use super::{Instance, Master, Word, SPI};
+
+/// A device on an SPI bus, selected by one of the hardware slave select signals
+///
+/// Multiple devices can share one SPI peripheral in master mode. Each of them
+/// is selected using its own slave select signal, which is driven by the SPI
+/// peripheral itself, so no GPIO pins need to be toggled in software.
+///
+/// You can get an instance of this struct by calling [`SPI::device`].
+///
+/// # `embedded-hal` traits
+///
+/// Implements the blocking SPI traits from embedded-hal 1.0 (alpha). Every
+/// call to one of those trait methods is executed as one transfer: The slave
+/// select signal is asserted before the first word is sent, and deasserted
+/// after the last word has been received. Use [`Transactional`] to combine
+/// multiple operations into one transfer.
+///
+/// Before the transfer starts, the mode and delays configured in the device's
+/// [`DeviceSettings`] are applied. The previous configuration of the SPI
+/// peripheral is restored after the transfer has ended.
+///
+/// [`SPI::device`]: struct.SPI.html#method.device
+/// [`Transactional`]: #impl-Transactional%3CW%3E
+/// [`DeviceSettings`]: struct.DeviceSettings.html
+pub struct Device<'bus, I, W>
+where
+    I: Instance,
+{
+    _spi: &'bus SPI<I, Enabled<Master<W>>>,
+    ssel: u8,
+    settings: DeviceSettings,
+}
+
+impl<'bus, I, W> Device<'bus, I, W>
+where
+    I: Instance,
+    W: Word,
+{
+    pub(super) fn new(
+        spi: &'bus SPI<I, Enabled<Master<W>>>,
+        ssel: u8,
+        settings: DeviceSettings,
+    ) -> Self {
+        let registers = Self::registers();
+
+        // The slave select polarity is configured right away, to make sure
+        // that the signal has the correct level while it is deasserted.
+        let mask = 0x1 << (8 + ssel);
+        registers.cfg.modify(|r, w| {
+            let bits = if settings.ssel_active_high {
+                r.bits() | mask
+            } else {
+                r.bits() & !mask
+            };
+
+            // Sound, as we only modify the SPOL bit of our own slave select.
+            unsafe { w.bits(bits) }
+        });
+
+        Self {
+            _spi: spi,
+            ssel,
+            settings,
+        }
+    }
+
+    /// Access the device settings
+    pub fn settings(&self) -> &DeviceSettings {
+        &self.settings
+    }
+
+    fn registers() -> &'static RegisterBlock {
+        // Sound, as we hold a reference to the enabled `SPI` instance, which
+        // means nobody else can modify its configuration, and all our accesses
+        // happen in blocking methods that require `&mut self`.
+        unsafe { &*I::REGISTERS }
+    }
+
+    fn transaction<R>(&mut self, f: impl FnOnce(&RegisterBlock) -> R) -> R {
+        let registers = Self::registers();
+
+        // All operations are blocking, so nothing should be going on. Better
+        // make sure, before changing the configuration.
+        while registers.stat.read().mstidle().bit_is_clear() {}
+
+        let cfg = registers.cfg.read().bits();
+        let dly = registers.dly.read().bits();
+        let txctl = registers.txctl.read().bits();
+
+        registers.cfg.modify(|_, w| {
+            match self.settings.mode.polarity {
+                Polarity::IdleHigh => w.cpol().high(),
+                Polarity::IdleLow => w.cpol().low(),
+            };
+            match self.settings.mode.phase {
+                Phase::CaptureOnFirstTransition => w.cpha().clear_bit(),
+                Phase::CaptureOnSecondTransition => w.cpha().set_bit(),
+            };
+            w
+        });
+        registers.dly.write(|w| {
+            // Sound, as the values have been checked by `DeviceSettings`.
+            unsafe {
+                w.pre_delay()
+                    .bits(self.settings.pre_delay)
+                    .post_delay()
+                    .bits(self.settings.post_delay)
+                    .frame_delay()
+                    .bits(self.settings.frame_delay)
+                    .transfer_delay()
+                    .bits(self.settings.transfer_delay)
+            }
+        });
+        registers.txctl.modify(|_, w| {
+            // The TXSSEL bits are active low. Only select our device.
+            w.txssel0_n().bit(self.ssel != 0);
+            w.txssel1_n().bit(self.ssel != 1);
+            w.txssel2_n().bit(self.ssel != 2);
+            w.txssel3_n().bit(self.ssel != 3);
+
+            // Every word is a frame, so the frame delay is inserted between
+            // words.
+            w.eof().set_bit();
+            w.eot().clear_bit();
+            w.rxignore().clear_bit()
+        });
+
+        let result = f(registers);
+
+        while registers.stat.read().mstidle().bit_is_clear() {}
+        registers.stat.write(|w| w.endtransfer().set_bit());
+        while registers.stat.read().mstidle().bit_is_clear() {}
+
+        // Sound, as we're restoring values we've read from the registers
+        // before.
+        registers.cfg.write(|w| unsafe { w.bits(cfg) });
+        registers.dly.write(|w| unsafe { w.bits(dly) });
+        registers.txctl.write(|w| unsafe { w.bits(txctl) });
+
+        result
+    }
+}
+
+fn transfer_word<W>(registers: &RegisterBlock, word: W) -> W
+where
+    W: Word,
+{
+    while registers.stat.read().txrdy().bit_is_clear() {}
+    registers
+        .txdat
+        .write(|w| unsafe { w.data().bits(word.into()) });
+
+    while registers.stat.read().rxrdy().bit_is_clear() {}
+    W::from_u16(registers.rxdat.read().rxdat().bits())
+}
+
+fn write_words<W>(registers: &RegisterBlock, words: impl IntoIterator<Item = W>)
+where
+    W: Word,
+{
+    // We don't need to read back the received words, if they're ignored.
+    registers.txctl.modify(|_, w| w.rxignore().set_bit());
+
+    for word in words {
+        while registers.stat.read().txrdy().bit_is_clear() {}
+        registers
+            .txdat
+            .write(|w| unsafe { w.data().bits(word.into()) });
+    }
+
+    while registers.stat.read().mstidle().bit_is_clear() {}
+    registers.txctl.modify(|_, w| w.rxignore().clear_bit());
+}
+
+fn execute<W>(registers: &RegisterBlock, operation: &mut Operation<W>)
+where
+    W: Word,
+{
+    match operation {
+        Operation::Read(words) => {
+            for word in words.iter_mut() {
+                *word = transfer_word(registers, W::from_u16(0));
+            }
+        }
+        Operation::Write(words) => {
+            write_words(registers, words.iter().copied());
+        }
+        Operation::Transfer(read, write) => {
+            let len = read.len().max(write.len());
+            for i in 0..len {
+                let word = write.get(i).copied().unwrap_or(W::from_u16(0));
+                let received = transfer_word(registers, word);
+                if let Some(word) = read.get_mut(i) {
+                    *word = received;
+                }
+            }
+        }
+        Operation::TransferInplace(words) => {
+            for word in words.iter_mut() {
+                *word = transfer_word(registers, *word);
+            }
+        }
+    }
+}
+
+impl<I, W> Transfer<W> for Device<'_, I, W>
+where
+    I: Instance,
+    W: Word + 'static,
+{
+    type Error = ErrorKind;
+
+    fn transfer(
+        &mut self,
+        read: &mut [W],
+        write: &[W],
+    ) -> Result<(), Self::Error> {
+        self.transaction(|registers| {
+            execute(registers, &mut Operation::Transfer(read, write))
+        });
+        Ok(())
+    }
+}
+
+impl<I, W> TransferInplace<W> for Device<'_, I, W>
+where
+    I: Instance,
+    W: Word + 'static,
+{
+    type Error = ErrorKind;
+
+    fn transfer_inplace(&mut self, words: &mut [W]) -> Result<(), Self::Error> {
+        self.transaction(|registers| {
+            execute(registers, &mut Operation::TransferInplace(words))
+        });
+        Ok(())
+    }
+}
+
+impl<I, W> Read<W> for Device<'_, I, W>
+where
+    I: Instance,
+    W: Word + 'static,
+{
+    type Error = ErrorKind;
+
+    fn read(&mut self, words: &mut [W]) -> Result<(), Self::Error> {
+        self.transaction(|registers| {
+            execute(registers, &mut Operation::Read(words))
+        });
+        Ok(())
+    }
+}
+
+impl<I, W> Write<W> for Device<'_, I, W>
+where
+    I: Instance,
+    W: Word + 'static,
+{
+    type Error = ErrorKind;
+
+    fn write(&mut self, words: &[W]) -> Result<(), Self::Error> {
+        self.transaction(|registers| {
+            execute(registers, &mut Operation::Write(words))
+        });
+        Ok(())
+    }
+}
+
+impl<I, W> WriteIter<W> for Device<'_, I, W>
+where
+    I: Instance,
+    W: Word,
+{
+    type Error = ErrorKind;
+
+    fn write_iter<WI>(&mut self, words: WI) -> Result<(), Self::Error>
+    where
+        WI: IntoIterator<Item = W>,
+    {
+        self.transaction(|registers| write_words(registers, words));
+        Ok(())
+    }
+}
+
+impl<I, W> Transactional<W> for Device<'_, I, W>
+where
+    I: Instance,
+    W: Word + 'static,
+{
+    type Error = ErrorKind;
+
+    /// Execute all operations as one transfer
+    ///
+    /// The slave select signal stays asserted between the operations.
+    fn exec(
+        &mut self,
+        operations: &mut [Operation<'_, W>],
+    ) -> Result<(), Self::Error> {
+        self.transaction(|registers| {
+            for operation in operations {
+                execute(registers, operation);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Settings for an SPI [`Device`]
+///
+/// Expected as an argument by [`SPI::device`]. All delays are specified as a
+/// number of SPI clock cycles, and can be in the range of 0 to 15.
+///
+/// [`Device`]: struct.Device.html
+/// [`SPI::device`]: struct.SPI.html#method.device
+#[derive(Clone, Copy)]
+pub struct DeviceSettings {
+    pub(super) mode: Mode,
+    pub(super) ssel_active_high: bool,
+    pub(super) pre_delay: u8,
+    pub(super) post_delay: u8,
+    pub(super) frame_delay: u8,
+    pub(super) transfer_delay: u8,
+}
+
+impl DeviceSettings {
+    /// Set the SPI mode
+    ///
+    /// Overwrites the previous mode setting. The default is [`MODE_0`].
+    ///
+    /// [`MODE_0`]: constant.MODE_0.html
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Make the slave select signal active low
+    ///
+    /// Overwrites the previous polarity setting. This is the default.
+    pub fn ssel_active_low(mut self) -> Self {
+        self.ssel_active_high = false;
+        self
+    }
+
+    /// Make the slave select signal active high
+    ///
+    /// Overwrites the previous polarity setting.
+    pub fn ssel_active_high(mut self) -> Self {
+        self.ssel_active_high = true;
+        self
+    }
+
+    /// Set the delay between slave select assertion and the first word
+    ///
+    /// Corresponds to the PRE_DELAY field in the DLY register. There's always
+    /// one SPI clock cycle between slave select assertion and the first clock
+    /// edge, in addition to this delay.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `clocks` is larger than 15.
+    pub fn pre_delay(mut self, clocks: u8) -> Self {
+        self.pre_delay = check_delay(clocks);
+        self
+    }
+
+    /// Set the delay between the last word and slave select deassertion
+    ///
+    /// Corresponds to the POST_DELAY field in the DLY register.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `clocks` is larger than 15.
+    pub fn post_delay(mut self, clocks: u8) -> Self {
+        self.post_delay = check_delay(clocks);
+        self
+    }
+
+    /// Set the minimum delay between two words
+    ///
+    /// Corresponds to the FRAME_DELAY field in the DLY register.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `clocks` is larger than 15.
+    pub fn frame_delay(mut self, clocks: u8) -> Self {
+        self.frame_delay = check_delay(clocks);
+        self
+    }
+
+    /// Set the additional time slave select is deasserted between transfers
+    ///
+    /// Corresponds to the TRANSFER_DELAY field in the DLY register. Slave
+    /// select is always deasserted for at least one SPI clock cycle, in
+    /// addition to this delay.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `clocks` is larger than 15.
+    pub fn transfer_delay(mut self, clocks: u8) -> Self {
+        self.transfer_delay = check_delay(clocks);
+        self
+    }
+}
+
+impl Default for DeviceSettings {
+    fn default() -> Self {
+        Self {
+            mode: MODE_0,
+            ssel_active_high: false,
+            pre_delay: 0,
+            post_delay: 0,
+            frame_delay: 0,
+            transfer_delay: 0,
+        }
+    }
+}
+
+fn check_delay(clocks: u8) -> u8 {
+    assert!(clocks <= 15);
+    clocks
+}
@@ -11,7 +11,7 @@ use crate::{
     pac::dma0::channel::xfercfg::{DSTINC_A, SRCINC_A},
 };
 
-use super::{Instance, Master, SPI};
+use super::{Instance, Master, Slave, Word, SPI};
 
 /// An SPI/DMA transfer
 ///
@@ -20,20 +20,21 @@ use super::{Instance, Master, SPI};
 /// wraps and manages two [`dma::Transfer`] structs under the hood.
 ///
 /// [`dma::Transfer`]: ../dma/struct.Transfer.html
-pub struct Transfer<State, I: Instance> {
-    spi: SPI<I, Enabled<Master>>,
-    buffer: &'static mut [u8],
-    rx_transfer: dma::Transfer<State, I::RxChannel, Rx<I>, dma::Buffer>,
-    tx_transfer: dma::Transfer<State, I::TxChannel, dma::Buffer, Tx<I>>,
+pub struct Transfer<State, I: Instance, W: 'static = u8> {
+    spi: SPI<I, Enabled<Master<W>>>,
+    buffer: &'static mut [W],
+    rx_transfer: dma::Transfer<State, I::RxChannel, Rx<I, W>, dma::Buffer<W>>,
+    tx_transfer: dma::Transfer<State, I::TxChannel, dma::Buffer<W>, Tx<I, W>>,
 }
 
-impl<I> Transfer<Ready, I>
+impl<I, W> Transfer<Ready, I, W>
 where
     I: Instance,
+    W: Word,
 {
     pub(super) fn new(
-        spi: SPI<I, Enabled<Master>>,
-        buffer: &'static mut [u8],
+        spi: SPI<I, Enabled<Master<W>>>,
+        buffer: &'static mut [W],
         rx_channel: dma::Channel<I::RxChannel, Enabled>,
         tx_channel: dma::Channel<I::TxChannel, Enabled>,
     ) -> Self {
@@ -68,7 +69,7 @@ where
     /// Start the transfer
     ///
     /// Starts both DMA transfers that are part of this SPI transfer.
-    pub fn start(self) -> Transfer<Started, I> {
+    pub fn start(self) -> Transfer<Started, I, W> {
         Transfer {
             spi: self.spi,
             buffer: self.buffer,
@@ -78,9 +79,10 @@ where
     }
 }
 
-impl<I> Transfer<Started, I>
+impl<I, W> Transfer<Started, I, W>
 where
     I: Instance,
+    W: Word,
 {
     /// Wait for the transfer to finish
     ///
@@ -88,8 +90,8 @@ where
     pub fn wait(
         self,
     ) -> (
-        SPI<I, Enabled<Master>>,
-        &'static mut [u8],
+        SPI<I, Enabled<Master<W>>>,
+        &'static mut [W],
         dma::Channel<I::RxChannel, Enabled>,
         dma::Channel<I::TxChannel, Enabled>,
     ) {
@@ -113,16 +115,191 @@ where
     }
 }
 
+/// An SPI/DMA transfer in slave mode
+///
+/// In slave mode, the master decides how many words are transferred. This
+/// `Transfer` therefore doesn't finish once a buffer has been sent or filled,
+/// but once the slave select signal has been deasserted. It wraps and manages
+/// two [`dma::Transfer`] structs under the hood, one for receiving and one for
+/// sending.
+///
+/// The transfer should be started before the master asserts the slave select
+/// signal. If the master clocks more words than fit into the receive buffer,
+/// the excess words are discarded. If it clocks more words than are available
+/// in the transmit buffer, a transmitter underrun occurs.
+///
+/// # Interrupts
+///
+/// To wait for the transfer to finish without blocking, enable the "Slave
+/// Select Deasserted" interrupt (see [`Interrupts`]) before starting the
+/// transfer. Once the interrupt fires, [`is_complete`] returns `true` and
+/// [`wait`] returns without blocking.
+///
+/// [`dma::Transfer`]: ../dma/struct.Transfer.html
+/// [`Interrupts`]: struct.Interrupts.html
+/// [`is_complete`]: #method.is_complete
+/// [`wait`]: #method.wait
+pub struct SlaveTransfer<State, I: Instance> {
+    spi: SPI<I, Enabled<Slave>>,
+    rx_transfer:
+        dma::Transfer<State, I::RxChannel, Rx<I, u8>, &'static mut [u8]>,
+    tx_transfer: dma::Transfer<State, I::TxChannel, &'static [u8], Tx<I, u8>>,
+}
+
+impl<I> SlaveTransfer<Ready, I>
+where
+    I: Instance,
+{
+    pub(super) fn new(
+        spi: SPI<I, Enabled<Slave>>,
+        rx_buffer: &'static mut [u8],
+        tx_buffer: &'static [u8],
+        rx_channel: dma::Channel<I::RxChannel, Enabled>,
+        tx_channel: dma::Channel<I::TxChannel, Enabled>,
+    ) -> Self {
+        let rx_transfer =
+            dma::Transfer::new(rx_channel, Rx(PhantomData), rx_buffer);
+        let tx_transfer =
+            dma::Transfer::new(tx_channel, tx_buffer, Tx(PhantomData));
+
+        Self {
+            spi,
+            rx_transfer,
+            tx_transfer,
+        }
+    }
+
+    /// Start the transfer
+    ///
+    /// Starts both DMA transfers that are part of this SPI transfer. Any words
+    /// that have been received before the transfer was started are discarded.
+    pub fn start(self) -> SlaveTransfer<Started, I> {
+        let registers = registers::<I>();
+
+        // Disabling the peripheral resets its internal state, getting rid of
+        // the dummy word in TXDAT (see `SPI::enable_as_slave`), which would
+        // otherwise be sent before the first word from the buffer.
+        registers.cfg.modify(|_, w| w.enable().disabled());
+        registers.cfg.modify(|_, w| w.enable().enabled());
+
+        // Clear any flags left over from earlier transfers.
+        registers.stat.write(|w| {
+            w.rxov().set_bit();
+            w.txur().set_bit();
+            w.ssa().set_bit();
+            w.ssd().set_bit()
+        });
+
+        SlaveTransfer {
+            spi: self.spi,
+            rx_transfer: self.rx_transfer.start(),
+            tx_transfer: self.tx_transfer.start(),
+        }
+    }
+}
+
+impl<I> SlaveTransfer<Started, I>
+where
+    I: Instance,
+{
+    /// Indicates whether the transfer has finished
+    ///
+    /// Returns `true`, once the master has deasserted the slave select signal.
+    /// Corresponds to the SSD flag in the STAT register.
+    pub fn is_complete(&self) -> bool {
+        // Can't read field through API. Issue:
+        // https://github.com/lpc-rs/lpc-pac/issues/52
+        registers::<I>().stat.read().bits() & (0x1 << 5) != 0
+    }
+
+    /// Wait for the transfer to finish
+    ///
+    /// Waits until the master has deasserted the slave select signal, then
+    /// stops both underlying DMA transfers.
+    pub fn wait(self) -> SlavePayload<I> {
+        let registers = registers::<I>();
+
+        while !self.is_complete() {}
+
+        // The last received word might not have been picked up by the DMA
+        // controller yet.
+        while self.rx_transfer.is_active()
+            && registers.stat.read().rxrdy().bit_is_set()
+        {}
+
+        let (rx_payload, remaining) = self.rx_transfer.abort();
+        let (tx_payload, _) = self.tx_transfer.abort();
+
+        registers.stat.write(|w| w.ssd().set_bit());
+
+        // Make sure there's a dummy word in TXDAT again, to prevent a TX
+        // underrun error when the `SPI` instance is used without DMA.
+        if registers.stat.read().txrdy().bit_is_set() {
+            registers.txdat.write(|w| unsafe { w.data().bits(0xff) });
+        }
+
+        let received = rx_payload.dest.len() - remaining as usize;
+
+        SlavePayload {
+            spi: self.spi,
+            rx_buffer: rx_payload.dest,
+            tx_buffer: tx_payload.source,
+            rx_channel: rx_payload.channel,
+            tx_channel: tx_payload.channel,
+            received,
+        }
+    }
+}
+
+/// The resources held by a [`SlaveTransfer`]
+///
+/// Returned by [`SlaveTransfer::wait`], once the transfer has finished.
+///
+/// [`SlaveTransfer`]: struct.SlaveTransfer.html
+/// [`SlaveTransfer::wait`]: struct.SlaveTransfer.html#method.wait
+pub struct SlavePayload<I: Instance> {
+    /// The SPI instance
+    pub spi: SPI<I, Enabled<Slave>>,
+
+    /// The buffer that the received words have been written to
+    pub rx_buffer: &'static mut [u8],
+
+    /// The buffer that words have been sent from
+    pub tx_buffer: &'static [u8],
+
+    /// The DMA channel used for receiving
+    pub rx_channel: dma::Channel<I::RxChannel, Enabled>,
+
+    /// The DMA channel used for sending
+    pub tx_channel: dma::Channel<I::TxChannel, Enabled>,
+
+    /// The number of words that have been received
+    ///
+    /// The received words are at the beginning of `rx_buffer`.
+    pub received: usize,
+}
+
+fn registers<I>() -> &'static crate::pac::spi0::RegisterBlock
+where
+    I: Instance,
+{
+    // Sound, because we're dereferencing a register address that is always
+    // valid on the target hardware, and the transfer owns the `SPI` instance.
+    unsafe { &*I::REGISTERS }
+}
+
 /// Represents the receiving portion of the DMA peripheral
-struct Rx<I>(PhantomData<I>);
+struct Rx<I, W>(PhantomData<(I, W)>);
 
-impl<I> crate::private::Sealed for Rx<I> {}
+impl<I, W> crate::private::Sealed for Rx<I, W> {}
 
-impl<I> dma::Source for Rx<I>
+impl<I, W> dma::Source for Rx<I, W>
 where
     I: Instance,
+    W: Word,
 {
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         true
@@ -152,15 +329,17 @@ where
 }
 
 /// Represents the sending portion of the DMA peripheral
-struct Tx<I>(PhantomData<I>);
+struct Tx<I, W>(PhantomData<(I, W)>);
 
-impl<I> crate::private::Sealed for Tx<I> {}
+impl<I, W> crate::private::Sealed for Tx<I, W> {}
 
-impl<I> dma::Dest for Tx<I>
+impl<I, W> dma::Dest for Tx<I, W>
 where
     I: Instance,
+    W: Word,
 {
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         true
@@ -33,7 +33,10 @@ pub trait Instance:
 }
 
 /// Implemented for slave select functions of a given SPI instance
-pub trait SlaveSelect<I>: private::Sealed {}
+pub trait SlaveSelect<I>: private::Sealed {
+    /// The number of the slave select signal (0 for SSEL0, 1 for SSEL1, etc.)
+    const INDEX: u8;
+}
 
 macro_rules! instances {
     (
@@ -43,7 +46,7 @@ macro_rules! instances {
             $sck:ident,
             $mosi:ident,
             $miso:ident,
-            [$($ssel:ident),*],
+            [$($ssel:ident = $ssel_index:expr),*],
             $rx_channel:ident,
             $tx_channel:ident;
         )*
@@ -70,7 +73,9 @@ macro_rules! instances {
             $(
                 impl private::Sealed for swm::$ssel {}
 
-                impl SlaveSelect<pac::$instance> for swm::$ssel {}
+                impl SlaveSelect<pac::$instance> for swm::$ssel {
+                    const INDEX: u8 = $ssel_index;
+                }
             )*
         )*
     };
@@ -80,11 +85,16 @@ macro_rules! instances {
 instances!(
     SPI0, 9,
         SPI0_SCK, SPI0_MOSI, SPI0_MISO,
-        [SPI0_SSEL0, SPI0_SSEL1, SPI0_SSEL2, SPI0_SSEL3],
+        [
+            SPI0_SSEL0 = 0,
+            SPI0_SSEL1 = 1,
+            SPI0_SSEL2 = 2,
+            SPI0_SSEL3 = 3
+        ],
         Channel6, Channel7;
     SPI1, 10,
         SPI1_SCK, SPI1_MOSI, SPI1_MISO,
-        [SPI1_SSEL0, SPI1_SSEL1],
+        [SPI1_SSEL0 = 0, SPI1_SSEL1 = 1],
         Channel8, Channel9;
 );
 
@@ -92,11 +102,16 @@ instances!(
 instances!(
     SPI0, 9,
         SPI0_SCK, SPI0_MOSI, SPI0_MISO,
-        [SPI0_SSEL0, SPI0_SSEL1, SPI0_SSEL2, SPI0_SSEL3],
+        [
+            SPI0_SSEL0 = 0,
+            SPI0_SSEL1 = 1,
+            SPI0_SSEL2 = 2,
+            SPI0_SSEL3 = 3
+        ],
         Channel10, Channel11;
     SPI1, 10,
         SPI1_SCK, SPI1_MOSI, SPI1_MISO,
-        [SPI1_SSEL0, SPI1_SSEL1],
+        [SPI1_SSEL0 = 0, SPI1_SSEL1 = 1],
         Channel12, Channel13;
 );
 
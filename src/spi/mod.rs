@@ -63,17 +63,19 @@
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 mod clock;
+mod device;
 mod dma;
 mod instances;
 mod interrupts;
 mod peripheral;
 
 pub use self::{
-    clock::{Clock, ClockSource},
-    dma::Transfer,
+    clock::{Clock, ClockError, ClockSource, Divider},
+    device::{Device, DeviceSettings},
+    dma::{SlavePayload, SlaveTransfer, Transfer},
     instances::{Instance, SlaveSelect},
     interrupts::Interrupts,
-    peripheral::{Master, Slave, SPI},
+    peripheral::{Master, Slave, Word, SPI},
 };
 
 pub use crate::embedded_hal::spi::{
@@ -1,15 +1,18 @@
-use core::convert::Infallible;
+use core::{convert::Infallible, marker::PhantomData};
 
 use embedded_hal::spi::{FullDuplex, Mode, Phase, Polarity};
 
 use crate::{
     dma::{self, transfer::state::Ready},
     init_state::{Disabled, Enabled},
-    pac::spi0::cfg::MASTER_A,
+    pac::{dma0::channel::xfercfg::DSTINC_A, spi0::cfg::MASTER_A},
     swm, syscon,
 };
 
-use super::{Clock, ClockSource, Instance, Interrupts, SlaveSelect, Transfer};
+use super::{
+    Clock, ClockSource, Device, DeviceSettings, Instance, Interrupts,
+    SlaveSelect, SlaveTransfer, Transfer,
+};
 
 /// Interface to a SPI peripheral
 ///
@@ -24,11 +27,15 @@ use super::{Clock, ClockSource, Instance, Interrupts, SlaveSelect, Transfer};
 /// - [`embedded_hal::blocking::spi::Transfer`] for synchronous transfers
 /// - [`embedded_hal::blocking::spi::Write`] for synchronous writes
 ///
+/// All of those are implemented for the word type selected in master mode
+/// (`u8` by default; see [`SPI::with_data_length`]).
+///
 /// [`Peripherals`]: ../struct.Peripherals.html
 /// [module documentation]: index.html
 /// [`embedded_hal::spi::FullDuplex`]: #impl-FullDuplex%3Cu8%3E
 /// [`embedded_hal::blocking::spi::Transfer`]: #impl-Transfer%3CW%3E
 /// [`embedded_hal::blocking::spi::Write`]: #impl-Write%3CW%3E
+/// [`SPI::with_data_length`]: #method.with_data_length
 pub struct SPI<I, State> {
     spi: I,
     _state: State,
@@ -83,7 +90,7 @@ where
 
         SPI {
             spi: self.spi,
-            _state: Enabled(Master),
+            _state: Enabled(Master(PhantomData)),
         }
     }
 
@@ -253,10 +260,238 @@ where
     }
 }
 
-impl<I> SPI<I, Enabled<Master>>
+impl<I, W> SPI<I, Enabled<Master<W>>>
 where
     I: Instance,
+    W: Word,
 {
+    /// Change the data length of the transmitted and received words
+    ///
+    /// By default, the SPI peripheral sends and receives words of 8 bits, which
+    /// are represented as `u8`. This method can be used to select any data
+    /// length from 4 to 16 bits. Words of more than 8 bits need to be
+    /// represented as `u16`, which is selected using the `NewWord` type
+    /// parameter.
+    ///
+    /// Words are sent and received right-aligned, meaning any bits beyond the
+    /// configured data length are ignored when sending, and zero when
+    /// receiving.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `bits` is less than 4, or larger than the number of bits in
+    /// `NewWord`.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// # use lpc8xx_hal::{prelude::*, Peripherals, spi};
+    /// #
+    /// # let mut p = Peripherals::take().unwrap();
+    /// # let mut swm = p.SWM.split();
+    /// # let mut syscon = p.SYSCON.split();
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// #
+    /// # let (sck, _) = swm.movable_functions.spi0_sck.assign(
+    /// #     p.pins.pio0_13.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (mosi, _) = swm.movable_functions.spi0_mosi.assign(
+    /// #     p.pins.pio0_14.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (miso, _) = swm.movable_functions.spi0_miso.assign(
+    /// #     p.pins.pio0_15.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let spi_clock = spi::Clock::new(&(), 0);
+    /// # #[cfg(feature = "845")]
+    /// # let spi_clock = spi::Clock::new(&syscon.iosc, 0);
+    /// #
+    /// let spi = p.SPI0.enable_as_master(
+    ///     &spi_clock,
+    ///     &mut syscon.handle,
+    ///     spi::MODE_0,
+    ///     sck,
+    ///     mosi,
+    ///     miso,
+    /// );
+    ///
+    /// // Talk to a 12-bit DAC
+    /// let mut spi = spi.with_data_length::<u16>(12);
+    /// spi.transmit_only(&[0x0fff, 0x0800, 0x0000]);
+    /// ```
+    pub fn with_data_length<NewWord>(
+        self,
+        bits: u8,
+    ) -> SPI<I, Enabled<Master<NewWord>>>
+    where
+        NewWord: Word,
+    {
+        assert!(bits >= 4 && bits <= NewWord::BITS);
+
+        // Wait until the previous configuration is no longer in use.
+        while !self.is_master_idle() {}
+
+        self.spi
+            .txctl
+            .modify(|_, w| unsafe { w.len().bits(bits - 1) });
+
+        SPI {
+            spi: self.spi,
+            _state: Enabled(Master(PhantomData)),
+        }
+    }
+
+    /// End the current transfer
+    ///
+    /// The SPI peripheral keeps the slave select signals asserted between
+    /// words, and between multiple calls to methods that send or receive
+    /// words. This allows you to combine multiple operations (for example a
+    /// command written with [`Write`], followed by a [`Transfer`] reading the
+    /// reply) into one transfer, without the slave select signal going inactive
+    /// in between.
+    ///
+    /// Call this method, once the transfer is complete. Slave select will be
+    /// deasserted, once the transmitter has finished sending any word it is
+    /// currently working on.
+    ///
+    /// Corresponds to the ENDTRANSFER bit in the STAT register.
+    ///
+    /// [`Write`]: #impl-Write%3CW%3E
+    /// [`Transfer`]: #impl-Transfer%3CW%3E
+    pub fn end_transfer(&mut self) {
+        self.spi.stat.write(|w| w.endtransfer().set_bit());
+    }
+
+    /// Send all words, ignoring any received words
+    ///
+    /// Sets the RXIGNORE bit in TXCTL while sending, which means there's no
+    /// need to wait for each word to be received before sending the next one.
+    /// This is faster than the [`Write`] implementation, which needs to read
+    /// back every word.
+    ///
+    /// Blocks until the last word has been sent completely.
+    ///
+    /// [`Write`]: #impl-Write%3CW%3E
+    pub fn transmit_only(&mut self, words: &[W]) {
+        self.spi.txctl.modify(|_, w| w.rxignore().set_bit());
+
+        for &word in words {
+            while !self.is_ready_to_transmit() {}
+            self.spi
+                .txdat
+                .write(|w| unsafe { w.data().bits(word.into()) });
+        }
+
+        // Wait until the last word has been sent completely. Callers expect
+        // the words to be sent when this method returns, and we mustn't change
+        // the control bits before then.
+        while !self.is_master_idle() {}
+
+        self.spi.txctl.modify(|_, w| w.rxignore().clear_bit());
+    }
+
+    /// Create a device that is selected by a hardware slave select signal
+    ///
+    /// The returned [`Device`] drives the provided slave select function
+    /// automatically. Multiple devices, each with its own slave select
+    /// function, can be created for the same SPI instance, allowing multiple
+    /// chips to share the bus.
+    ///
+    /// Sets the polarity of the slave select signal, as configured in
+    /// `settings`, right away. All other settings are applied whenever the
+    /// device starts a transfer.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// # use lpc8xx_hal::{prelude::*, Peripherals, spi};
+    /// use lpc8xx_hal::embedded_hal_alpha::spi::blocking::{
+    ///     Operation, Transactional, Write as _,
+    /// };
+    /// #
+    /// # let mut p = Peripherals::take().unwrap();
+    /// # let mut swm = p.SWM.split();
+    /// # let mut syscon = p.SYSCON.split();
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let mut swm_handle = swm.handle;
+    /// # #[cfg(feature = "845")]
+    /// # let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+    /// #
+    /// # let (sck, _) = swm.movable_functions.spi0_sck.assign(
+    /// #     p.pins.pio0_13.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (mosi, _) = swm.movable_functions.spi0_mosi.assign(
+    /// #     p.pins.pio0_14.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// # let (miso, _) = swm.movable_functions.spi0_miso.assign(
+    /// #     p.pins.pio0_15.into_swm_pin(),
+    /// #     &mut swm_handle,
+    /// # );
+    /// #
+    /// # #[cfg(feature = "82x")]
+    /// # let spi_clock = spi::Clock::new(&(), 0);
+    /// # #[cfg(feature = "845")]
+    /// # let spi_clock = spi::Clock::new(&syscon.iosc, 0);
+    /// #
+    /// let (ssel0, _) = swm.movable_functions.spi0_ssel0.assign(
+    ///     p.pins.pio0_16.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    /// let (ssel1, _) = swm.movable_functions.spi0_ssel1.assign(
+    ///     p.pins.pio0_17.into_swm_pin(),
+    ///     &mut swm_handle,
+    /// );
+    ///
+    /// let spi = p.SPI0.enable_as_master(
+    ///     &spi_clock,
+    ///     &mut syscon.handle,
+    ///     spi::MODE_0,
+    ///     sck,
+    ///     mosi,
+    ///     miso,
+    /// );
+    ///
+    /// let mut flash = spi.device(
+    ///     ssel0,
+    ///     spi::DeviceSettings::default().post_delay(2),
+    /// );
+    /// let mut sensor = spi.device(
+    ///     ssel1,
+    ///     spi::DeviceSettings::default()
+    ///         .mode(spi::MODE_3)
+    ///         .ssel_active_high(),
+    /// );
+    ///
+    /// let mut id = [0; 3];
+    /// flash
+    ///     .exec(&mut [Operation::Write(&[0x9f]), Operation::Read(&mut id)])
+    ///     .expect("Transfer shouldn't fail");
+    /// sensor.write(&[0x20, 0x01]).expect("Write shouldn't fail");
+    /// ```
+    ///
+    /// [`Device`]: struct.Device.html
+    pub fn device<Ssel, SselPin>(
+        &self,
+        _ssel: swm::Function<Ssel, swm::state::Assigned<SselPin>>,
+        settings: DeviceSettings,
+    ) -> Device<'_, I, W>
+    where
+        Ssel: SlaveSelect<I>,
+    {
+        Device::new(self, Ssel::INDEX, settings)
+    }
+
     /// Start an SPI transfer using DMA
     ///
     /// Sends all words in the provided buffer, writing the replies back into
@@ -267,18 +502,58 @@ where
     /// Panics, if the length of `buffer` is 0 or larger than 1024.
     pub fn transfer_all(
         self,
-        buffer: &'static mut [u8],
+        buffer: &'static mut [W],
         rx_channel: dma::Channel<I::RxChannel, Enabled>,
         tx_channel: dma::Channel<I::TxChannel, Enabled>,
-    ) -> Transfer<Ready, I> {
+    ) -> Transfer<Ready, I, W> {
         Transfer::new(self, buffer, rx_channel, tx_channel)
     }
+
+    /// Send all words in the provided buffer using DMA
+    ///
+    /// Received words are ignored by the peripheral (the RXIGNORE bit in TXCTL
+    /// is set while the transfer is ongoing), so only one DMA channel is
+    /// required, and no words need to be read back.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    pub fn write_all(
+        self,
+        buffer: &'static [W],
+        channel: dma::Channel<I::TxChannel, Enabled>,
+    ) -> dma::Transfer<Ready, I::TxChannel, &'static [W], Self> {
+        self.spi.txctl.modify(|_, w| w.rxignore().set_bit());
+        dma::Transfer::new(channel, buffer, self)
+    }
 }
 
 impl<I> SPI<I, Enabled<Slave>>
 where
     I: Instance,
 {
+    /// Prepare an SPI transfer in slave mode using DMA
+    ///
+    /// Words clocked by the master are written into `rx_buffer`, while words
+    /// from `tx_buffer` are sent in return. The transfer finishes when the
+    /// master deasserts the slave select signal. Please refer to
+    /// [`SlaveTransfer`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of any buffer is 0 or larger than 1024.
+    ///
+    /// [`SlaveTransfer`]: struct.SlaveTransfer.html
+    pub fn transfer_all(
+        self,
+        rx_buffer: &'static mut [u8],
+        tx_buffer: &'static [u8],
+        rx_channel: dma::Channel<I::RxChannel, Enabled>,
+        tx_channel: dma::Channel<I::TxChannel, Enabled>,
+    ) -> SlaveTransfer<Ready, I> {
+        SlaveTransfer::new(self, rx_buffer, tx_buffer, rx_channel, tx_channel)
+    }
+
     /// Receive a word
     pub fn receive(&mut self) -> nb::Result<u8, RxOverrunError> {
         let stat = self.spi.stat.read();
@@ -334,46 +609,98 @@ impl<I, State> SPI<I, State> {
     }
 }
 
-impl<I: Instance> FullDuplex<u8> for SPI<I, Enabled<Master>> {
+impl<I, W> FullDuplex<W> for SPI<I, Enabled<Master<W>>>
+where
+    I: Instance,
+    W: Word,
+{
     type Error = Infallible;
 
-    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+    fn read(&mut self) -> nb::Result<W, Self::Error> {
         if self.spi.stat.read().rxrdy().bit_is_clear() {
             return Err(nb::Error::WouldBlock);
         }
 
-        Ok(self.spi.rxdat.read().rxdat().bits() as u8)
+        Ok(W::from_u16(self.spi.rxdat.read().rxdat().bits()))
     }
 
-    fn send(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+    fn send(&mut self, word: W) -> nb::Result<(), Self::Error> {
         if self.spi.stat.read().txrdy().bit_is_clear() {
             return Err(nb::Error::WouldBlock);
         }
 
         self.spi
             .txdat
-            .write(|w| unsafe { w.data().bits(word as u16) });
+            .write(|w| unsafe { w.data().bits(word.into()) });
 
         Ok(())
     }
 }
 
-impl<I: Instance> embedded_hal::blocking::spi::transfer::Default<u8>
-    for SPI<I, Enabled<Master>>
+impl<I, W> embedded_hal::blocking::spi::transfer::Default<W>
+    for SPI<I, Enabled<Master<W>>>
+where
+    I: Instance,
+    W: Word,
+{
+}
+
+impl<I, W> embedded_hal::blocking::spi::write::Default<W>
+    for SPI<I, Enabled<Master<W>>>
+where
+    I: Instance,
+    W: Word,
 {
 }
 
-impl<I: Instance> embedded_hal::blocking::spi::write::Default<u8>
-    for SPI<I, Enabled<Master>>
+impl<I, W> crate::private::Sealed for SPI<I, Enabled<Master<W>>> {}
+
+impl<I, W> dma::Dest for SPI<I, Enabled<Master<W>>>
+where
+    I: Instance,
+    W: Word,
 {
+    type Error = Infallible;
+    type Word = W;
+
+    fn is_valid(&self) -> bool {
+        true
+    }
+
+    fn is_full(&self) -> bool {
+        false
+    }
+
+    fn increment(&self) -> DSTINC_A {
+        DSTINC_A::NO_INCREMENT
+    }
+
+    fn transfer_count(&self) -> Option<u16> {
+        None
+    }
+
+    fn end_addr(&mut self) -> *mut u8 {
+        &self.spi.txdat as *const _ as *mut u8
+    }
+
+    fn finish(&mut self) -> nb::Result<(), Self::Error> {
+        if !self.is_master_idle() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.spi.txctl.modify(|_, w| w.rxignore().clear_bit());
+
+        Ok(())
+    }
 }
 
 /// Indicates that SPI is in master mode
 ///
-/// Used as a type parameter on [`SPI`].
+/// Used as a type parameter on [`SPI`]. The type parameter `W` is the type of
+/// word that is sent and received.
 ///
 /// [`SPI`]: struct.SPI.html
-pub struct Master;
+pub struct Master<W = u8>(PhantomData<W>);
 
 /// Indicates that SPI is in slave mode
 ///
@@ -382,6 +709,37 @@ pub struct Master;
 /// [`SPI`]: struct.SPI.html
 pub struct Slave;
 
+/// Implemented for types that represent a supported word size
+///
+/// The SPI peripheral supports data lengths of 4 to 8 bits (represented by
+/// `u8`) and up to 16 bits (represented by `u16`).
+pub trait Word: dma::Word + Copy + Into<u16> {
+    /// The maximum number of bits that can be represented by this type
+    const BITS: u8;
+
+    /// Converts a `u16` to `Self`
+    ///
+    /// Intended for internal use only. The value will never be out of range,
+    /// as long as the data length matches the word type.
+    fn from_u16(w: u16) -> Self;
+}
+
+impl Word for u8 {
+    const BITS: u8 = 8;
+
+    fn from_u16(w: u16) -> Self {
+        w as u8
+    }
+}
+
+impl Word for u16 {
+    const BITS: u8 = 16;
+
+    fn from_u16(w: u16) -> Self {
+        w
+    }
+}
+
 /// Receiver Overrun Error
 #[derive(Debug)]
 pub struct RxOverrunError;
@@ -0,0 +1,124 @@
+use crate::{init_state, pins};
+
+use super::{fixed_functions, handle::Handle};
+
+/// The pin assignments from before functions were detached from pins
+///
+/// Returned by [`Handle::detach`]. Pass it to [`Handle::reattach`] to restore
+/// the previous assignments.
+pub(crate) struct Detached {
+    pinassign: [u32; PINASSIGN],
+    pinenable: [u32; PINENABLE],
+}
+
+macro_rules! detach {
+    (
+        [$($pinassign:ident),*]
+        [$($pinenable:ident),*]
+    ) => {
+        impl Handle<init_state::Enabled> {
+            /// Temporarily detach all functions from the provided pins
+            ///
+            /// Detaching the functions gives control of the pins back to GPIO,
+            /// without affecting the type state of any functions or pins. The
+            /// caller must restore the previous assignments using
+            /// [`reattach`], before handing back control of the pins.
+            ///
+            /// [`reattach`]: #method.reattach
+            pub(crate) fn detach(
+                &mut self,
+                pins: &[&dyn pins::Trait],
+            ) -> Detached {
+                let detached = Detached {
+                    pinassign: [$(self.swm.$pinassign.read().bits(),)*],
+                    pinenable: [$(self.swm.$pinenable.read().bits(),)*],
+                };
+
+                for pin in pins {
+                    let number = pin.id() | pin.port() << 5;
+
+                    $(
+                        let mut bytes =
+                            self.swm.$pinassign.read().bits().to_le_bytes();
+                        for byte in &mut bytes {
+                            if *byte == number {
+                                *byte = 0xff;
+                            }
+                        }
+
+                        // Sound, as 0xff is a valid value for all fields and
+                        // denotes an unassigned function.
+                        self.swm.$pinassign.write(|w| unsafe {
+                            w.bits(u32::from_le_bytes(bytes))
+                        });
+                    )*
+
+                    fixed_functions::detach(self, number);
+                }
+
+                detached
+            }
+
+            /// Restore the assignments from before [`detach`] was called
+            ///
+            /// [`detach`]: #method.detach
+            pub(crate) fn reattach(&mut self, detached: Detached) {
+                let mut pinassign = detached.pinassign.iter();
+                let mut pinenable = detached.pinenable.iter();
+
+                $(
+                    if let Some(&bits) = pinassign.next() {
+                        // Sound, as we're writing back a value that we read
+                        // from the same register.
+                        self.swm.$pinassign.write(|w| unsafe { w.bits(bits) });
+                    }
+                )*
+                $(
+                    if let Some(&bits) = pinenable.next() {
+                        // Sound, as we're writing back a value that we read
+                        // from the same register.
+                        self.swm.$pinenable.write(|w| unsafe { w.bits(bits) });
+                    }
+                )*
+            }
+        }
+    };
+}
+
+#[cfg(feature = "82x")]
+const PINASSIGN: usize = 12;
+#[cfg(feature = "82x")]
+const PINENABLE: usize = 1;
+
+#[cfg(feature = "82x")]
+detach!(
+    [
+        pinassign0,
+        pinassign1,
+        pinassign2,
+        pinassign3,
+        pinassign4,
+        pinassign5,
+        pinassign6,
+        pinassign7,
+        pinassign8,
+        pinassign9,
+        pinassign10,
+        pinassign11
+    ][pinenable0]
+);
+
+#[cfg(feature = "845")]
+const PINASSIGN: usize = 15;
+#[cfg(feature = "845")]
+const PINENABLE: usize = 2;
+
+#[cfg(feature = "845")]
+detach!(
+    [
+        pinassign0, pinassign1, pinassign2, pinassign3, pinassign4,
+        pinassign5, pinassign6, pinassign7, pinassign8, pinassign9,
+        pinassign10, pinassign11, pinassign12, pinassign13, pinassign14
+    ]
+    [pinenable0, pinenable1]
+);
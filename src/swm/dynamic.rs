@@ -0,0 +1,183 @@
+//! Runtime assignment of movable functions
+//!
+//! The regular SWM API tracks function assignments in the type system, which
+//! requires the pin to be known at compile time. If that is not possible, for
+//! example because the pin configuration is read from external memory at
+//! startup, movable functions can be assigned to pins that are in the
+//! [`SwmDynamic`] state instead. Such pins are created using
+//! [`Pin::into_dynamic_swm_pin`].
+//!
+//! Since the type system can't help in that case, [`Function::assign_dynamic`]
+//! checks the switch matrix registers and returns an [`Error`], if the
+//! assignment would conflict with the existing configuration.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{pins, Peripherals};
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut swm = p.SWM.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let mut swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let mut swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! let mut my_pins: [pins::Pin<pins::GenericPin, pins::state::SwmDynamic>; 2] = [
+//!     p.pins.pio0_12.into_dynamic_swm_pin(),
+//!     p.pins.pio0_13.into_dynamic_swm_pin(),
+//! ];
+//!
+//! // Read from external memory in a real application
+//! let index = 1;
+//!
+//! let u0_txd = swm
+//!     .movable_functions
+//!     .u0_txd
+//!     .assign_dynamic(&mut my_pins[index], &mut swm_handle)
+//!     .map_err(|(error, _)| error)
+//!     .unwrap();
+//!
+//! // Assigning a second output function to the same pin fails
+//! let result = swm
+//!     .movable_functions
+//!     .u1_txd
+//!     .assign_dynamic(&mut my_pins[index], &mut swm_handle);
+//! assert!(result.is_err());
+//!
+//! let u0_txd = u0_txd.unassign_dynamic(&mut swm_handle);
+//! ```
+//!
+//! [`SwmDynamic`]: ../../pins/state/struct.SwmDynamic.html
+//! [`Pin::into_dynamic_swm_pin`]: ../../pins/struct.Pin.html#method.into_dynamic_swm_pin
+//! [`Function::assign_dynamic`]: ../struct.Function.html#method.assign_dynamic
+//! [`Error`]: enum.Error.html
+
+use core::marker::PhantomData;
+
+use crate::pins::{self, GenericPin, Pin, Trait as _};
+
+use super::{
+    fixed_functions,
+    functions::Function,
+    handle::Handle,
+    movable_functions,
+    state::{Assigned, Unassigned},
+};
+
+impl<T> Function<T, Unassigned>
+where
+    T: MovableFunction,
+{
+    /// Assign this movable function to a pin that is selected at runtime
+    ///
+    /// This is the runtime-checked counterpart to [`Function::assign`]. Please
+    /// prefer [`Function::assign`], if the pin is known at compile time.
+    ///
+    /// The state of the pin is not changed, as [`SwmDynamic`] doesn't track
+    /// assigned functions. The returned `Function` can be unassigned again
+    /// using [`Function::unassign_dynamic`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the function is already assigned to a pin, if an
+    /// output function is assigned and the pin already has an output function,
+    /// or if a fixed function is enabled on the pin. This `Function` is
+    /// returned along with the error, and the switch matrix is left unchanged.
+    ///
+    /// Please refer to the [module documentation] for an example.
+    ///
+    /// [`Function::assign`]: #method.assign
+    /// [`SwmDynamic`]: ../pins/state/struct.SwmDynamic.html
+    /// [`Function::unassign_dynamic`]: #method.unassign_dynamic
+    /// [module documentation]: dynamic/index.html
+    pub fn assign_dynamic(
+        self,
+        pin: &mut Pin<GenericPin, pins::state::SwmDynamic>,
+        swm: &mut Handle,
+    ) -> Result<Function<T, Assigned<GenericPin>>, (Error, Self)> {
+        let number = pin.ty.id() | pin.ty.port() << 5;
+
+        if T::read(swm) != UNASSIGNED {
+            return Err((Error::FunctionAssigned, self));
+        }
+        if fixed_functions::is_enabled_on(swm, number) {
+            return Err((Error::FixedFunction, self));
+        }
+        if T::OUTPUT && movable_functions::has_output(swm, number) {
+            return Err((Error::PinInUse, self));
+        }
+
+        T::write(swm, number);
+
+        Ok(Function {
+            ty: self.ty,
+            _state: Assigned(PhantomData),
+        })
+    }
+}
+
+impl<T> Function<T, Assigned<GenericPin>>
+where
+    T: MovableFunction,
+{
+    /// Unassign this movable function from the pin it was assigned to
+    ///
+    /// This is the counterpart to [`Function::assign_dynamic`].
+    ///
+    /// [`Function::assign_dynamic`]: #method.assign_dynamic
+    pub fn unassign_dynamic(self, swm: &mut Handle) -> Function<T, Unassigned> {
+        T::write(swm, UNASSIGNED);
+
+        Function {
+            ty: self.ty,
+            _state: Unassigned,
+        }
+    }
+
+    /// Returns the port and id of the pin this function is assigned to
+    pub fn pin(&self, swm: &Handle) -> (u8, u8) {
+        let number = T::read(swm);
+        (number >> 5, number & 0x1f)
+    }
+}
+
+/// Indicates that a function could not be assigned at runtime
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The function is already assigned to a pin
+    FunctionAssigned,
+
+    /// The pin already has an output function assigned
+    PinInUse,
+
+    /// A fixed function is enabled on the pin
+    FixedFunction,
+}
+
+/// Implemented for all movable functions
+///
+/// This trait is an internal implementation detail and should neither be
+/// implemented nor used outside of LPC8xx HAL. Any changes to this trait won't
+/// be considered breaking changes.
+///
+/// Please refer to [`Function::assign_dynamic`] for the public API that uses
+/// this trait.
+///
+/// [`Function::assign_dynamic`]: ../struct.Function.html#method.assign_dynamic
+pub trait MovableFunction {
+    /// Whether this is an output (or bidirectional) function
+    const OUTPUT: bool;
+
+    /// Internal method to read the pin number from the PINASSIGN register
+    fn read(swm: &Handle) -> u8;
+
+    /// Internal method to write the pin number to the PINASSIGN register
+    fn write(swm: &mut Handle, number: u8);
+}
+
+/// The value of a PINASSIGN field, if the function is not assigned
+const UNASSIGNED: u8 = 0xff;
@@ -35,6 +35,43 @@ macro_rules! fixed_functions {
         }
 
 
+        /// Disable all fixed functions on the pin with the provided number
+        pub(super) fn detach(swm: &mut Handle, number: u8) {
+            $(
+                if number == pins::$pin::NUMBER {
+                    swm.swm.$register.modify(|_, w| w.$field().set_bit());
+                }
+            )*
+        }
+
+        /// The names of all fixed functions, and the numbers of their pins
+        pub(super) const PINS: &[(&str, u8)] =
+            &[$((stringify!($type), pins::$pin::NUMBER),)*];
+
+        /// The number of fixed functions
+        pub(super) const COUNT: usize = PINS.len();
+
+        /// Read whether each fixed function is enabled
+        ///
+        /// The order of the returned array matches [`PINS`].
+        pub(super) fn read_all(swm: &Handle) -> [bool; COUNT] {
+            [$(swm.swm.$register.read().$field().bit_is_clear(),)*]
+        }
+
+        /// Whether a fixed function is enabled on the pin with the provided
+        /// number
+        pub(super) fn is_enabled_on(swm: &Handle, number: u8) -> bool {
+            $(
+                if number == pins::$pin::NUMBER
+                    && swm.swm.$register.read().$field().bit_is_clear()
+                {
+                    return true;
+                }
+            )*
+
+            false
+        }
+
         $(
             /// Represents a fixed function
             ///
@@ -22,8 +22,8 @@ use super::{
 /// [`FixedFunctions`]: struct.FixedFunctions.html
 /// [`MovableFunctions`]: struct.MovableFunctions.html
 pub struct Function<T, S> {
-    ty: T,
-    _state: S,
+    pub(super) ty: T,
+    pub(super) _state: S,
 }
 
 impl<T, S> Function<T, S>
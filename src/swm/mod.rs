@@ -3,15 +3,25 @@
 //! The entry point to this API is [`SWM`]. Please refer to [`SWM`]'s
 //! documentation for additional information.
 //!
+//! If the pins are not known at compile time, movable functions can be
+//! assigned at runtime instead. Please refer to the [`dynamic`] module.
+//!
+//! The current assignments can be inspected using the [`query`] module.
+//!
 //! The switch matrix is described in the following user manuals:
 //! - LPC82x user manual, chapter 7
 //! - LPC84x user manual, chapter 10
 //!
 //! [`SWM`]: struct.SWM.html
+//! [`dynamic`]: dynamic/index.html
+//! [`query`]: query/index.html
 
+pub mod dynamic;
+pub mod query;
 pub mod state;
 
 mod assignment;
+mod detach;
 mod fixed_functions;
 mod function_kind;
 mod functions;
@@ -1,6 +1,7 @@
 use crate::pins::{self, Trait as _};
 
 use super::{
+    dynamic::MovableFunction,
     function_kind::{Input, Output},
     functions::{Function, FunctionTrait},
     handle::Handle,
@@ -35,6 +36,31 @@ macro_rules! movable_functions {
             }
         }
 
+        /// The names of all movable functions
+        pub(super) const NAMES: &[&str] = &[$(stringify!($type),)*];
+
+        /// The number of movable functions
+        pub(super) const COUNT: usize = NAMES.len();
+
+        /// Read the pin numbers that all movable functions are assigned to
+        ///
+        /// The order of the returned array matches [`NAMES`].
+        pub(super) fn read_all(swm: &Handle) -> [u8; COUNT] {
+            [$($type::read(swm),)*]
+        }
+
+        /// Whether an output function is assigned to the pin with the
+        /// provided number
+        pub(super) fn has_output(swm: &Handle, number: u8) -> bool {
+            $(
+                if $type::OUTPUT && $type::read(swm) == number {
+                    return true;
+                }
+            )*
+
+            false
+        }
+
 
         $(
             /// Represents a movable function
@@ -45,6 +71,20 @@ macro_rules! movable_functions {
             #[allow(non_camel_case_types)]
             pub struct $type(());
 
+            impl MovableFunction for $type {
+                const OUTPUT: bool = is_output!($kind);
+
+                fn read(swm: &Handle) -> u8 {
+                    swm.swm.$reg_name.read().$reg_field().bits()
+                }
+
+                fn write(swm: &mut Handle, number: u8) {
+                    swm.swm
+                        .$reg_name
+                        .modify(|_, w| unsafe { w.$reg_field().bits(number) });
+                }
+            }
+
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_0 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_1 );
             impl_function!($type, $kind, $reg_name, $reg_field, PIO0_2 );
@@ -103,6 +143,15 @@ macro_rules! movable_functions {
     }
 }
 
+macro_rules! is_output {
+    (Input) => {
+        false
+    };
+    (Output) => {
+        true
+    };
+}
+
 macro_rules! impl_function {
     (
         $type:ident,
@@ -240,7 +289,7 @@ movable_functions!(
     t0_mat1      , T0_MAT1      , Output, pinassign13, t0_mat1;
     t0_mat2      , T0_MAT2      , Output, pinassign13, t0_mat2;
     t0_mat3      , T0_MAT3      , Output, pinassign14, t0_mat3;
-    t0_cap0      , T0_CAP0      , Output, pinassign14, t0_cap0;
-    t0_cap1      , T0_CAP1      , Output, pinassign14, t0_cap1;
-    t0_cap2      , T0_CAP2      , Output, pinassign14, t0_cap2;
+    t0_cap0      , T0_CAP0      , Input , pinassign14, t0_cap0;
+    t0_cap1      , T0_CAP1      , Input , pinassign14, t0_cap1;
+    t0_cap2      , T0_CAP2      , Input , pinassign14, t0_cap2;
 );
@@ -0,0 +1,298 @@
+//! Introspection of the switch matrix configuration
+//!
+//! [`Handle::snapshot`] reads the switch matrix and GPIO registers, and
+//! returns a [`Snapshot`] that lists the movable functions, fixed functions,
+//! and GPIO state of every pin. This is mainly useful for debugging, as the
+//! [`Display`] implementation of [`Snapshot`] prints all pins in a readable
+//! format, one pin per line. If the `defmt` feature is enabled,
+//! `defmt::Format` is implemented too.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use lpc8xx_hal::Peripherals;
+//!
+//! let p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let swm = p.SWM.split();
+//!
+//! #[cfg(feature = "82x")]
+//! let swm_handle = swm.handle;
+//! #[cfg(feature = "845")]
+//! let swm_handle = swm.handle.enable(&mut syscon.handle);
+//!
+//! let snapshot = swm_handle.snapshot();
+//!
+//! for pin in snapshot.pins() {
+//!     if pin.movable_functions().any(|name| name == "U0_TXD") {
+//!         // found the pin that U0_TXD is assigned to
+//!     }
+//! }
+//!
+//! // `Snapshot` implements `Display`, so it can be printed to a USART using
+//! // `write!`.
+//! ```
+//!
+//! [`Handle::snapshot`]: ../struct.Handle.html#method.snapshot
+//! [`Snapshot`]: struct.Snapshot.html
+//! [`Display`]: https://doc.rust-lang.org/core/fmt/trait.Display.html
+
+use core::fmt;
+
+use crate::{
+    gpio::{Level, PORTS},
+    init_state, pac,
+    pins::{self, DynamicPinDirection},
+};
+
+use super::{fixed_functions, handle::Handle, movable_functions};
+
+impl Handle<init_state::Enabled> {
+    /// Read the current switch matrix and GPIO configuration
+    ///
+    /// This method only reads from registers, and doesn't affect the state of
+    /// any pins or functions. Please refer to the [module documentation] for
+    /// more information.
+    ///
+    /// [module documentation]: query/index.html
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            movable: movable_functions::read_all(self),
+            fixed: fixed_functions::read_all(self),
+            gpio: read_gpio(),
+        }
+    }
+}
+
+/// The switch matrix and GPIO configuration at a given point in time
+///
+/// Returned by [`Handle::snapshot`]. Please refer to the
+/// [module documentation] for more information.
+///
+/// [`Handle::snapshot`]: ../struct.Handle.html#method.snapshot
+/// [module documentation]: index.html
+#[derive(Clone, Copy)]
+pub struct Snapshot {
+    movable: [u8; movable_functions::COUNT],
+    fixed: [bool; fixed_functions::COUNT],
+    gpio: Option<[(u32, u32); PORTS]>,
+}
+
+impl Snapshot {
+    /// Returns an iterator over all pins
+    ///
+    /// Iterates over all pins in [`pins::Pins`], in order.
+    ///
+    /// [`pins::Pins`]: ../../pins/struct.Pins.html
+    pub fn pins(&self) -> impl Iterator<Item = PinInfo<'_>> {
+        pins::ALL.iter().map(move |&(port, id)| PinInfo {
+            port,
+            id,
+            snapshot: self,
+        })
+    }
+
+    /// Returns information about the pin with the provided port and id
+    ///
+    /// Returns `None`, if no such pin exists in [`pins::Pins`].
+    ///
+    /// [`pins::Pins`]: ../../pins/struct.Pins.html
+    pub fn pin(&self, port: u8, id: u8) -> Option<PinInfo<'_>> {
+        self.pins().find(|pin| pin.port == port && pin.id == id)
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for pin in self.pins() {
+            writeln!(f, "{}", pin)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Snapshot {
+    fn format(&self, f: defmt::Formatter) {
+        for pin in self.pins() {
+            defmt::write!(f, "{}\n", pin);
+        }
+    }
+}
+
+/// The configuration of a single pin, as recorded in a [`Snapshot`]
+///
+/// [`Snapshot`]: struct.Snapshot.html
+#[derive(Clone, Copy)]
+pub struct PinInfo<'s> {
+    port: u8,
+    id: u8,
+    snapshot: &'s Snapshot,
+}
+
+impl<'s> PinInfo<'s> {
+    /// Returns the port of the pin
+    pub fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// Returns the id of the pin within its port
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the names of the movable functions assigned to the pin
+    pub fn movable_functions(&self) -> impl Iterator<Item = &'static str> + 's {
+        let number = self.number();
+
+        movable_functions::NAMES
+            .iter()
+            .zip(self.snapshot.movable.iter())
+            .filter(move |&(_, &assigned)| assigned == number)
+            .map(|(&name, _)| name)
+    }
+
+    /// Returns the names of the fixed functions enabled on the pin
+    pub fn fixed_functions(&self) -> impl Iterator<Item = &'static str> + 's {
+        let number = self.number();
+
+        fixed_functions::PINS
+            .iter()
+            .zip(self.snapshot.fixed.iter())
+            .filter(move |&(&(_, pin), &enabled)| enabled && pin == number)
+            .map(|(&(name, _), _)| name)
+    }
+
+    /// Returns the GPIO state of the pin
+    ///
+    /// Returns `None`, if the GPIO peripheral was disabled when the snapshot
+    /// was taken. Please note that assigned functions take precedence over
+    /// GPIO, so the GPIO direction might not reflect the actual function of
+    /// the pin.
+    pub fn gpio(&self) -> Option<Gpio> {
+        let (dir, pin) = self.snapshot.gpio?[self.port as usize];
+        let mask = 0x1 << self.id;
+
+        let direction = if dir & mask != 0 {
+            DynamicPinDirection::Output
+        } else {
+            DynamicPinDirection::Input
+        };
+        let level = if pin & mask != 0 {
+            Level::High
+        } else {
+            Level::Low
+        };
+
+        Some(Gpio { direction, level })
+    }
+
+    fn number(&self) -> u8 {
+        self.id | self.port << 5
+    }
+}
+
+impl fmt::Display for PinInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PIO{}_{}:", self.port, self.id)?;
+
+        for name in self.movable_functions() {
+            write!(f, " {}", name)?;
+        }
+        for name in self.fixed_functions() {
+            write!(f, " {} (fixed)", name)?;
+        }
+
+        match self.gpio() {
+            Some(gpio) => {
+                write!(f, " GPIO {} {}", gpio.direction_str(), gpio.level_str())
+            }
+            None => write!(f, " GPIO disabled"),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for PinInfo<'_> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "PIO{=u8}_{=u8}:", self.port, self.id);
+
+        for name in self.movable_functions() {
+            defmt::write!(f, " {=str}", name);
+        }
+        for name in self.fixed_functions() {
+            defmt::write!(f, " {=str} (fixed)", name);
+        }
+
+        match self.gpio() {
+            Some(gpio) => defmt::write!(
+                f,
+                " GPIO {=str} {=str}",
+                gpio.direction_str(),
+                gpio.level_str()
+            ),
+            None => defmt::write!(f, " GPIO disabled"),
+        }
+    }
+}
+
+/// The GPIO state of a pin, as recorded in a [`Snapshot`]
+///
+/// [`Snapshot`]: struct.Snapshot.html
+#[derive(Clone, Copy, Debug)]
+pub struct Gpio {
+    /// The direction configured in the DIR register
+    pub direction: DynamicPinDirection,
+
+    /// The level read from the PIN register
+    pub level: Level,
+}
+
+impl Gpio {
+    fn direction_str(&self) -> &'static str {
+        match self.direction {
+            DynamicPinDirection::Input => "input",
+            DynamicPinDirection::Output => "output",
+        }
+    }
+
+    fn level_str(&self) -> &'static str {
+        match self.level {
+            Level::High => "high",
+            Level::Low => "low",
+        }
+    }
+}
+
+/// Read the DIR and PIN registers of all ports
+///
+/// Returns `None`, if the GPIO clock is disabled.
+fn read_gpio() -> Option<[(u32, u32); PORTS]> {
+    // Sound, as we're only reading from the registers.
+    let syscon = unsafe { &*pac::SYSCON::ptr() };
+    let gpio = unsafe { &*pac::GPIO::ptr() };
+
+    #[cfg(feature = "82x")]
+    {
+        if syscon.sysahbclkctrl.read().gpio().bit_is_clear() {
+            return None;
+        }
+
+        Some([(gpio.dir0.read().bits(), gpio.pin0.read().bits())])
+    }
+
+    #[cfg(feature = "845")]
+    {
+        let clkctrl = syscon.sysahbclkctrl0.read();
+        if clkctrl.gpio0().bit_is_clear() || clkctrl.gpio1().bit_is_clear() {
+            return None;
+        }
+
+        Some([
+            (gpio.dir[0].read().bits(), gpio.pin[0].read().bits()),
+            (gpio.dir[1].read().bits(), gpio.pin[1].read().bits()),
+        ])
+    }
+}
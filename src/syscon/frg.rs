@@ -1,6 +1,7 @@
 //! The fractional generator (FRG), available on LPC845
 
 use crate::{
+    clock::{self, Frequency as _},
     pac::{
         self,
         syscon::frg::{FRGCLKSEL, FRGDIV, FRGMULT},
@@ -8,6 +9,8 @@ use crate::{
     reg_proxy::{Reg, RegProxy},
 };
 
+use super::{main_clock, IOSC};
+
 /// Clock sources for the FRG
 ///
 /// Can be passed to [`FRG::select_clock`].
@@ -50,6 +53,40 @@ where
         // Safe, as all `u8` values are valid.
         self.mult.write(|w| unsafe { w.bits(mult.into()) });
     }
+
+    /// Returns the frequency of the selected clock source
+    ///
+    /// # Panics
+    ///
+    /// Panics, if no clock source is selected, or if the selected clock is
+    /// derived from a clock of unknown frequency.
+    pub fn input_hz(&self) -> u32 {
+        match self.clksel.read().sel().variant() {
+            Clock::FRO => IOSC::new().hz(),
+            Clock::MAIN_CLK => main_clock::main_clock_hz(),
+            Clock::SYS_PLL => main_clock::pll_output_hz(),
+            Clock::NONE => panic!("No FRG clock source selected"),
+        }
+    }
+}
+
+impl<I> clock::Frequency for FRG<I>
+where
+    I: Instance,
+{
+    /// Computes the output frequency from the register configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics, if no clock source is selected, or if the selected clock is
+    /// derived from a clock of unknown frequency.
+    fn hz(&self) -> u32 {
+        main_clock::frg_output_hz(
+            self.input_hz(),
+            self.div.read().div().bits(),
+            self.mult.read().mult().bits(),
+        )
+    }
 }
 
 /// Implemented for all FRG instances
@@ -0,0 +1,117 @@
+//! Frequencies of the main clock and the clocks derived from it
+//!
+//! The frequencies are computed from the current register configuration. The
+//! IRC/FRO is assumed to run at the frequency reported by [`IOSC`]. Clocks of
+//! unknown frequency (external clock input, crystal oscillator, watchdog
+//! oscillator) can't be handled and result in a panic.
+//!
+//! [`IOSC`]: ../struct.IOSC.html
+
+use crate::{clock::Frequency as _, pac};
+
+use super::IOSC;
+
+/// Returns the frequency of the main clock
+///
+/// # Panics
+///
+/// Panics, if the main clock is derived from a clock of unknown frequency.
+pub(crate) fn main_clock_hz() -> u32 {
+    // Sound, as we're only reading from registers.
+    let syscon = unsafe { &*pac::SYSCON::ptr() };
+
+    #[cfg(feature = "82x")]
+    {
+        match syscon.mainclksel.read().sel().bits() {
+            0 => IOSC::new().hz(),
+            1 => pll_input_hz(),
+            3 => pll_output_hz(),
+            _ => unknown("watchdog oscillator"),
+        }
+    }
+
+    #[cfg(feature = "845")]
+    {
+        if syscon.mainclkpllsel.read().sel().is_sys_pll() {
+            return pll_output_hz();
+        }
+
+        match syscon.mainclksel.read().sel().bits() {
+            0 => IOSC::new().hz(),
+            1 => unknown("external clock"),
+            2 => unknown("watchdog oscillator"),
+            _ => IOSC::new().hz() / 2,
+        }
+    }
+}
+
+/// Returns the frequency of the system clock
+///
+/// The system clock is the main clock, divided by SYSAHBCLKDIV. It clocks the
+/// CPU and the peripherals.
+///
+/// # Panics
+///
+/// Panics, if the main clock is derived from a clock of unknown frequency.
+pub(crate) fn system_clock_hz() -> u32 {
+    // Sound, as we're only reading from the register.
+    let syscon = unsafe { &*pac::SYSCON::ptr() };
+
+    // A divider of 0 disables the system clock, which can't be the case while
+    // this code is running.
+    let div = syscon.sysahbclkdiv.read().div().bits().max(1);
+
+    main_clock_hz() / u32::from(div)
+}
+
+/// Returns the frequency of the system PLL output
+///
+/// # Panics
+///
+/// Panics, if the PLL input is a clock of unknown frequency.
+pub(crate) fn pll_output_hz() -> u32 {
+    // Sound, as we're only reading from the register.
+    let syscon = unsafe { &*pac::SYSCON::ptr() };
+
+    let msel = syscon.syspllctrl.read().msel().bits();
+    pll_input_hz() * (u32::from(msel) + 1)
+}
+
+/// Returns the frequency of the system PLL input
+fn pll_input_hz() -> u32 {
+    // Sound, as we're only reading from the register.
+    let syscon = unsafe { &*pac::SYSCON::ptr() };
+
+    #[cfg(feature = "82x")]
+    {
+        match syscon.syspllclksel.read().sel().bits() {
+            0 => IOSC::new().hz(),
+            1 => unknown("crystal oscillator"),
+            _ => unknown("external clock"),
+        }
+    }
+
+    #[cfg(feature = "845")]
+    {
+        match syscon.syspllclksel.read().sel().bits() {
+            0 => IOSC::new().hz(),
+            1 => unknown("external clock"),
+            2 => unknown("watchdog oscillator"),
+            _ => IOSC::new().hz() / 2,
+        }
+    }
+}
+
+/// Returns the frequency of a fractional generator's output
+///
+/// The fractional generator divides its input by `1 + mult / (div + 1)`.
+pub(crate) fn frg_output_hz(input_hz: u32, div: u8, mult: u8) -> u32 {
+    let div = u64::from(div) + 1;
+    let mult = u64::from(mult);
+
+    (u64::from(input_hz) * div / (div + mult)) as u32
+}
+
+fn unknown(source: &str) -> ! {
+    panic!("Frequency of {} is unknown", source)
+}
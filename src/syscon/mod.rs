@@ -17,18 +17,21 @@ pub use self::frg::FRG;
 
 pub mod clock_source;
 
+mod main_clock;
+
 #[cfg(feature = "82x")]
 use crate::pac::syscon::{
     pdruncfg, presetctrl as presetctrl0, starterp1,
-    sysahbclkctrl as sysahbclkctrl0, PDRUNCFG, PRESETCTRL as PRESETCTRL0,
-    STARTERP1, SYSAHBCLKCTRL as SYSAHBCLKCTRL0, UARTCLKDIV, UARTFRGDIV,
-    UARTFRGMULT,
+    sysahbclkctrl as sysahbclkctrl0, MAINCLKSEL, MAINCLKUEN, PDAWAKECFG,
+    PDRUNCFG, PRESETCTRL as PRESETCTRL0, STARTERP1,
+    SYSAHBCLKCTRL as SYSAHBCLKCTRL0, UARTCLKDIV, UARTFRGDIV, UARTFRGMULT,
 };
 
 #[cfg(feature = "845")]
 use crate::pac::syscon::{
-    pdruncfg, presetctrl0, starterp1, sysahbclkctrl0, FCLKSEL, PDRUNCFG,
-    PRESETCTRL0, STARTERP1, SYSAHBCLKCTRL0,
+    pdruncfg, presetctrl0, starterp1, sysahbclkctrl0, FCLKSEL, MAINCLKPLLSEL,
+    MAINCLKPLLUEN, MAINCLKSEL, MAINCLKUEN, PDAWAKECFG, PDRUNCFG, PRESETCTRL0,
+    STARTERP0, STARTERP1, SYSAHBCLKCTRL0,
 };
 
 use crate::{clock, init_state, pac, reg_proxy::RegProxy};
@@ -67,8 +70,17 @@ impl SYSCON {
     pub fn split(self) -> Parts {
         Parts {
             handle: Handle {
+                mainclksel: RegProxy::new(),
+                mainclkuen: RegProxy::new(),
+                #[cfg(feature = "845")]
+                mainclkpllsel: RegProxy::new(),
+                #[cfg(feature = "845")]
+                mainclkplluen: RegProxy::new(),
+                pdawakecfg: RegProxy::new(),
                 pdruncfg: RegProxy::new(),
                 presetctrl0: RegProxy::new(),
+                #[cfg(feature = "845")]
+                starterp0: RegProxy::new(),
                 starterp1: RegProxy::new(),
                 sysahbclkctrl: RegProxy::new(),
                 #[cfg(feature = "845")]
@@ -93,6 +105,7 @@ impl SYSCON {
             },
 
             iosc_derived_clock: IoscDerivedClock::new(),
+            system_clock: SystemClock(()),
             #[cfg(feature = "845")]
             frg0: FRG::new(),
             #[cfg(feature = "845")]
@@ -161,6 +174,9 @@ pub struct Parts {
     /// The 750 kHz internal oscillator/IRC/FRO-derived clock
     pub iosc_derived_clock: IoscDerivedClock<init_state::Enabled>,
 
+    /// The system clock
+    pub system_clock: SystemClock,
+
     #[cfg(feature = "845")]
     /// Fractional Baud Rate Generator 0
     pub frg0: FRG<frg::FRG0>,
@@ -181,8 +197,17 @@ pub struct Parts {
 ///
 /// [module documentation]: index.html
 pub struct Handle {
+    mainclksel: RegProxy<MAINCLKSEL>,
+    mainclkuen: RegProxy<MAINCLKUEN>,
+    #[cfg(feature = "845")]
+    mainclkpllsel: RegProxy<MAINCLKPLLSEL>,
+    #[cfg(feature = "845")]
+    mainclkplluen: RegProxy<MAINCLKPLLUEN>,
+    pdawakecfg: RegProxy<PDAWAKECFG>,
     pdruncfg: RegProxy<PDRUNCFG>,
     presetctrl0: RegProxy<PRESETCTRL0>,
+    #[cfg(feature = "845")]
+    starterp0: RegProxy<STARTERP0>,
     starterp1: RegProxy<STARTERP1>,
     sysahbclkctrl: RegProxy<SYSAHBCLKCTRL0>,
     #[cfg(feature = "845")]
@@ -253,6 +278,112 @@ impl Handle {
     {
         self.starterp1.modify(|_, w| I::disable(w));
     }
+
+    /// Enable pin interrupt wake-up from deep-sleep and power-down modes
+    ///
+    /// `mask` selects the pin interrupts, with bit 0 representing PININT0.
+    #[cfg(feature = "845")]
+    pub(crate) fn enable_pin_interrupt_wakeup(&mut self, mask: u8) {
+        // Sound, as bits 0 to 7 of STARTERP0 represent the pin interrupts.
+        self.starterp0
+            .modify(|r, w| unsafe { w.bits(r.bits() | mask as u32) });
+    }
+
+    /// Disable pin interrupt wake-up from deep-sleep and power-down modes
+    #[cfg(feature = "845")]
+    pub(crate) fn disable_pin_interrupt_wakeup(&mut self, mask: u8) {
+        // Sound, as bits 0 to 7 of STARTERP0 represent the pin interrupts.
+        self.starterp0
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(mask as u32)) });
+    }
+
+    /// Prepare the system for entering deep-sleep or power-down mode
+    ///
+    /// Selects the IRC/FRO as the main clock, as required by the user manual
+    /// (section 6.7.5.2), and copies the current power configuration into
+    /// PDAWAKECFG, so the analog blocks are restored to the state the HAL API
+    /// tracks, when the system wakes up.
+    ///
+    /// Returns the previous main clock selection, which must be passed to
+    /// [`Handle::restore_main_clock`] after waking up.
+    ///
+    /// [`Handle::restore_main_clock`]: #method.restore_main_clock
+    pub(crate) fn prepare_deep_sleep(&mut self) -> MainClockSelection {
+        let selection = MainClockSelection {
+            sel: self.mainclksel.read().sel().bits(),
+            #[cfg(feature = "845")]
+            pll_sel: self.mainclkpllsel.read().sel().bits(),
+        };
+
+        // Both registers share the same layout.
+        let pdruncfg = self.pdruncfg.read().bits();
+        self.pdawakecfg.write(|w| unsafe { w.bits(pdruncfg) });
+
+        self.select_main_clock(MainClockSelection {
+            sel: 0,
+            #[cfg(feature = "845")]
+            pll_sel: 0,
+        });
+
+        selection
+    }
+
+    /// Restore a main clock selection after waking up
+    ///
+    /// The system PLL is powered down in deep-sleep and power-down mode. If
+    /// the selection uses the PLL output, the IRC/FRO stays selected until the
+    /// PLL has locked again.
+    pub(crate) fn restore_main_clock(&mut self, selection: MainClockSelection) {
+        if selection.uses_pll_output() {
+            // Sound, as we're only reading from a read-only register.
+            let syscon = unsafe { &*pac::SYSCON::ptr() };
+            while syscon.syspllstat.read().lock().bit_is_clear() {}
+        }
+
+        self.select_main_clock(selection);
+    }
+
+    fn select_main_clock(&mut self, selection: MainClockSelection) {
+        // Sound, as the values have been read from the same registers, or are
+        // the reset values.
+        self.mainclksel
+            .write(|w| unsafe { w.bits(selection.sel as u32) });
+
+        // The new selection only takes effect after toggling the update enable
+        // register. See user manual, section 5.6.
+        self.mainclkuen.write(|w| w.ena().clear_bit());
+        self.mainclkuen.write(|w| w.ena().set_bit());
+
+        #[cfg(feature = "845")]
+        {
+            self.mainclkpllsel
+                .write(|w| unsafe { w.bits(selection.pll_sel as u32) });
+            self.mainclkplluen.write(|w| w.ena().clear_bit());
+            self.mainclkplluen.write(|w| w.ena().set_bit());
+        }
+    }
+}
+
+/// A main clock selection, as saved by `Handle::prepare_deep_sleep`
+pub(crate) struct MainClockSelection {
+    sel: u8,
+    #[cfg(feature = "845")]
+    pll_sel: u8,
+}
+
+impl MainClockSelection {
+    /// Indicates whether the main clock is the output of the system PLL
+    fn uses_pll_output(&self) -> bool {
+        #[cfg(feature = "82x")]
+        {
+            self.sel == 3
+        }
+
+        #[cfg(feature = "845")]
+        {
+            self.pll_sel == 1
+        }
+    }
 }
 
 /// Brown-out detection
@@ -281,6 +412,12 @@ pub struct FLASH(());
 #[derive(Debug)]
 pub struct IOSC(());
 
+impl IOSC {
+    pub(crate) fn new() -> Self {
+        IOSC(())
+    }
+}
+
 /// IOSC output
 ///
 /// Can be used to control IRC/FRO output using various methods on
@@ -370,6 +507,38 @@ impl UARTFRG {
     pub fn set_frgdiv(&mut self, value: u8) {
         self.uartfrgdiv.write(|w| unsafe { w.div().bits(value) });
     }
+
+    /// Returns the frequency of the fractional generator input
+    ///
+    /// That is the main clock, divided by UARTCLKDIV.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if UARTCLKDIV is 0, which disables U_PCLK, or if the main clock
+    /// is derived from a clock of unknown frequency.
+    pub fn input_hz(&self) -> u32 {
+        let div = self.uartclkdiv.read().div().bits();
+        assert!(div != 0, "U_PCLK is disabled (UARTCLKDIV is 0)");
+
+        main_clock::main_clock_hz() / u32::from(div)
+    }
+}
+
+#[cfg(feature = "82x")]
+impl clock::Frequency for UARTFRG {
+    /// Computes the frequency of U_PCLK from the register configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics, if UARTCLKDIV is 0, which disables U_PCLK, or if the main clock
+    /// is derived from a clock of unknown frequency.
+    fn hz(&self) -> u32 {
+        main_clock::frg_output_hz(
+            self.input_hz(),
+            self.uartfrgdiv.read().div().bits(),
+            self.uartfrgmult.read().mult().bits(),
+        )
+    }
 }
 
 /// Internal trait for controlling peripheral clocks
@@ -670,6 +839,25 @@ impl IoscDerivedClock<init_state::Disabled> {
     }
 }
 
+/// The system clock
+///
+/// The system clock is the main clock, divided by SYSAHBCLKDIV. It runs the
+/// CPU and the peripherals, including the MRT and CTIMER. Its frequency is
+/// computed from the register configuration (MAINCLKSEL, the system PLL, and
+/// SYSAHBCLKDIV), assuming the IRC/FRO runs at the frequency reported by
+/// [`IOSC`].
+///
+/// [`IOSC`]: struct.IOSC.html
+#[derive(Debug)]
+pub struct SystemClock(());
+
+impl clock::Frequency for IOSC {
+    /// Assumes the IRC/FRO runs at its default frequency of 12 MHz
+    fn hz(&self) -> u32 {
+        12_000_000
+    }
+}
+
 impl<State> clock::Frequency for IoscDerivedClock<State> {
     fn hz(&self) -> u32 {
         750_000
@@ -678,6 +866,20 @@ impl<State> clock::Frequency for IoscDerivedClock<State> {
 
 impl clock::Enabled for IoscDerivedClock<init_state::Enabled> {}
 
+impl clock::Frequency for SystemClock {
+    /// Computes the frequency of the system clock from the register
+    /// configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the main clock is derived from a clock of unknown frequency.
+    fn hz(&self) -> u32 {
+        main_clock::system_clock_hz()
+    }
+}
+
+impl clock::Enabled for SystemClock {}
+
 /// Internal trait used to configure interrupt wake-up
 ///
 /// This trait is an internal implementation detail and should neither be
@@ -734,11 +936,20 @@ wakeup_interrupt!(WktWakeup, wkt);
 wakeup_interrupt!(I2c2Wakeup, i2c2);
 wakeup_interrupt!(I2c3Wakeup, i2c3);
 
+reg!(MAINCLKSEL, MAINCLKSEL, pac::SYSCON, mainclksel);
+reg!(MAINCLKUEN, MAINCLKUEN, pac::SYSCON, mainclkuen);
+#[cfg(feature = "845")]
+reg!(MAINCLKPLLSEL, MAINCLKPLLSEL, pac::SYSCON, mainclkpllsel);
+#[cfg(feature = "845")]
+reg!(MAINCLKPLLUEN, MAINCLKPLLUEN, pac::SYSCON, mainclkplluen);
+reg!(PDAWAKECFG, PDAWAKECFG, pac::SYSCON, pdawakecfg);
 reg!(PDRUNCFG, PDRUNCFG, pac::SYSCON, pdruncfg);
 #[cfg(feature = "82x")]
 reg!(PRESETCTRL0, PRESETCTRL0, pac::SYSCON, presetctrl);
 #[cfg(feature = "845")]
 reg!(PRESETCTRL0, PRESETCTRL0, pac::SYSCON, presetctrl0);
+#[cfg(feature = "845")]
+reg!(STARTERP0, STARTERP0, pac::SYSCON, starterp0);
 reg!(STARTERP1, STARTERP1, pac::SYSCON, starterp1);
 #[cfg(feature = "82x")]
 reg!(SYSAHBCLKCTRL0, SYSAHBCLKCTRL0, pac::SYSCON, sysahbclkctrl);
@@ -1,6 +1,11 @@
 use core::marker::PhantomData;
 
-use crate::syscon::{self, clock_source::PeripheralClockSelector};
+use crate::{
+    clock,
+    syscon::{self, clock_source::PeripheralClockSelector},
+};
+
+use super::state::AsyncMode;
 
 /// Defines the clock configuration for a USART instance
 ///
@@ -39,6 +44,219 @@ where
     }
 }
 
+impl<T> Clock<T, AsyncMode>
+where
+    T: ClockSource,
+{
+    /// Create the clock configuration for a given baud rate
+    ///
+    /// Computes the configuration from the frequency of the clock source. See
+    /// [`BaudrateConfig::calculate`] for details. If you need to know the
+    /// baud rate that is actually achieved, use that method directly, then
+    /// pass the result to [`Clock::from_config`].
+    ///
+    /// [`BaudrateConfig::calculate`]: struct.BaudrateConfig.html#method.calculate
+    /// [`Clock::from_config`]: #method.from_config
+    pub fn from_baudrate(clock: &T, baudrate: u32) -> Result<Self, ClockError>
+    where
+        T: clock::Frequency,
+    {
+        let config = BaudrateConfig::calculate(clock.hz(), baudrate)?;
+        Ok(Self::from_config(clock, &config))
+    }
+
+    /// Create the clock configuration from a computed configuration
+    ///
+    /// If `config` was computed using [`BaudrateConfig::calculate_with_frg`],
+    /// the fractional generator that is passed as `clock` must be configured
+    /// accordingly.
+    ///
+    /// [`BaudrateConfig::calculate_with_frg`]: struct.BaudrateConfig.html#method.calculate_with_frg
+    pub fn from_config(clock: &T, config: &BaudrateConfig) -> Self {
+        Self::new(clock, config.brgval, config.osrval)
+    }
+}
+
+/// Baud rate configuration for a USART instance in asynchronous mode
+///
+/// This is computed by [`BaudrateConfig::calculate`] or
+/// [`BaudrateConfig::calculate_with_frg`], independently of any hardware. Use
+/// [`Clock::from_config`] to create a USART clock configuration from it.
+///
+/// [`BaudrateConfig::calculate`]: #method.calculate
+/// [`BaudrateConfig::calculate_with_frg`]: #method.calculate_with_frg
+/// [`Clock::from_config`]: struct.Clock.html#method.from_config
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BaudrateConfig {
+    /// The value of the BRGVAL field
+    ///
+    /// The clock is divided by `brgval + 1`.
+    pub brgval: u16,
+
+    /// The oversampling rate (5-16)
+    pub osrval: u8,
+
+    /// The multiplier of the fractional generator
+    ///
+    /// This is only relevant, if a fractional generator is used as the clock
+    /// source, in which case its divider must be set to 255, and its
+    /// multiplier to this value. [`Clock::from_baudrate_with_frg`] does this
+    /// automatically. It is always 0 for configurations computed by
+    /// [`calculate`].
+    ///
+    /// [`Clock::from_baudrate_with_frg`]: struct.Clock.html#method.from_baudrate_with_frg
+    /// [`calculate`]: #method.calculate
+    pub frg_mult: u8,
+
+    /// The resulting baud rate
+    pub baudrate: u32,
+
+    /// The deviation of the resulting baud rate from the requested one
+    ///
+    /// Specified in parts per million.
+    pub error_ppm: i32,
+}
+
+impl BaudrateConfig {
+    /// The maximum deviation from the requested baud rate that is accepted
+    const MAX_ERROR_PPM: i32 = 50_000;
+
+    /// Compute the configuration for a given baud rate
+    ///
+    /// `clock_hz` is the frequency of the USART clock source. Searches for the
+    /// combination of BRGVAL and oversampling rate that gets closest to
+    /// `baudrate`, preferring higher oversampling rates, which are more
+    /// robust against noise.
+    ///
+    /// This method is relatively computationally expensive, so it is
+    /// recommended to only use it during initialization.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClockError::BaudrateOutOfRange`], if `baudrate` is 0. Returns
+    /// [`ClockError::NotAccurate`], if no configuration is accurate within 5%.
+    ///
+    /// # Examples
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::usart::{BaudrateConfig, ClockError};
+    ///
+    /// let config = BaudrateConfig::calculate(12_000_000, 115_200).unwrap();
+    /// assert!(config.error_ppm.abs() < 10_000);
+    ///
+    /// let config = BaudrateConfig::calculate(12_000_000, 9600).unwrap();
+    /// assert_eq!(config.baudrate, 9600);
+    ///
+    /// assert_eq!(
+    ///     BaudrateConfig::calculate(12_000_000, 3_000_000),
+    ///     Err(ClockError::NotAccurate),
+    /// );
+    /// ```
+    ///
+    /// [`ClockError::BaudrateOutOfRange`]: enum.ClockError.html#variant.BaudrateOutOfRange
+    /// [`ClockError::NotAccurate`]: enum.ClockError.html#variant.NotAccurate
+    pub fn calculate(clock_hz: u32, baudrate: u32) -> Result<Self, ClockError> {
+        Self::search(clock_hz, baudrate, 0)
+    }
+
+    /// Compute the configuration for a baud rate, using a fractional generator
+    ///
+    /// Works like [`calculate`], but additionally searches for the best
+    /// multiplier of a fractional generator, which can significantly improve
+    /// accuracy. `clock_hz` is the frequency of the clock that is fed into
+    /// the fractional generator.
+    ///
+    /// The fractional generator needs to be configured with a divider of 255
+    /// and the multiplier from [`frg_mult`]. [`Clock::from_baudrate_with_frg`]
+    /// computes the configuration and configures the fractional generator in
+    /// one step.
+    ///
+    /// # Examples
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::usart::BaudrateConfig;
+    ///
+    /// let config =
+    ///     BaudrateConfig::calculate_with_frg(12_000_000, 115_200).unwrap();
+    /// assert!(config.error_ppm.abs() < 1_000);
+    /// ```
+    ///
+    /// [`calculate`]: #method.calculate
+    /// [`frg_mult`]: #structfield.frg_mult
+    /// [`Clock::from_baudrate_with_frg`]: struct.Clock.html#method.from_baudrate_with_frg
+    pub fn calculate_with_frg(
+        clock_hz: u32,
+        baudrate: u32,
+    ) -> Result<Self, ClockError> {
+        Self::search(clock_hz, baudrate, 255)
+    }
+
+    fn search(
+        clock_hz: u32,
+        baudrate: u32,
+        max_mult: u8,
+    ) -> Result<Self, ClockError> {
+        if baudrate == 0 {
+            return Err(ClockError::BaudrateOutOfRange);
+        }
+
+        let mut best: Option<Self> = None;
+
+        for osrval in (5..=16u8).rev() {
+            for frg_mult in 0..=max_mult {
+                // The fractional generator divides by `1 + mult / 256`.
+                let numerator = u64::from(clock_hz) * 256;
+                let frg = 256 + u64::from(frg_mult);
+                let denominator = u64::from(baudrate) * u64::from(osrval) * frg;
+
+                // Round to the nearest divider.
+                let div = ((numerator * 2 + denominator) / (denominator * 2))
+                    .clamp(1, 0x1_0000);
+
+                let actual =
+                    (numerator / (frg * u64::from(osrval) * div)) as u32;
+                let error_ppm = clock::error_ppm(actual, baudrate);
+
+                let is_better = match &best {
+                    Some(best) => error_ppm.abs() < best.error_ppm.abs(),
+                    None => true,
+                };
+                if is_better {
+                    best = Some(Self {
+                        brgval: (div - 1) as u16,
+                        osrval,
+                        frg_mult,
+                        baudrate: actual,
+                        error_ppm,
+                    });
+                }
+            }
+        }
+
+        match best {
+            Some(best) if best.error_ppm.abs() < Self::MAX_ERROR_PPM => {
+                Ok(best)
+            }
+            _ => Err(ClockError::NotAccurate),
+        }
+    }
+}
+
+/// Error computing a USART clock configuration
+///
+/// Returned by [`BaudrateConfig::calculate`] and [`Clock::from_baudrate`].
+///
+/// [`BaudrateConfig::calculate`]: struct.BaudrateConfig.html#method.calculate
+/// [`Clock::from_baudrate`]: struct.Clock.html#method.from_baudrate
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockError {
+    /// The requested baud rate is 0
+    BaudrateOutOfRange,
+
+    /// No configuration is accurate within 5% of the requested baud rate
+    NotAccurate,
+}
+
 /// Implemented for USART clock sources
 pub trait ClockSource: private::Sealed {
     /// Select the clock source
@@ -56,9 +274,12 @@ pub trait ClockSource: private::Sealed {
 
 #[cfg(feature = "82x")]
 mod target {
-    use crate::syscon::{self, UARTFRG};
+    use crate::{
+        syscon::{self, UARTFRG},
+        usart::state::AsyncMode,
+    };
 
-    use super::ClockSource;
+    use super::{BaudrateConfig, Clock, ClockError, ClockSource};
 
     impl super::private::Sealed for UARTFRG {}
 
@@ -67,6 +288,39 @@ mod target {
             // nothing to do; selected by default
         }
     }
+
+    impl Clock<UARTFRG, AsyncMode> {
+        /// Create the clock configuration for a given baud rate, using the FRG
+        ///
+        /// Computes the configuration as described in
+        /// [`BaudrateConfig::calculate_with_frg`], from the frequency of the
+        /// fractional generator input, and configures the fractional
+        /// generator accordingly.
+        ///
+        /// UARTCLKDIV must have been set before calling this method, using
+        /// [`UARTFRG::set_clkdiv`]. As the fractional generator is shared
+        /// between all USART instances, this changes their baud rates too.
+        ///
+        /// # Panics
+        ///
+        /// Panics, if UARTCLKDIV is 0, or if the main clock is derived from a
+        /// clock of unknown frequency.
+        ///
+        /// [`BaudrateConfig::calculate_with_frg`]: struct.BaudrateConfig.html#method.calculate_with_frg
+        /// [`UARTFRG::set_clkdiv`]: ../syscon/struct.UARTFRG.html#method.set_clkdiv
+        pub fn from_baudrate_with_frg(
+            frg: &mut UARTFRG,
+            baudrate: u32,
+        ) -> Result<Self, ClockError> {
+            let config =
+                BaudrateConfig::calculate_with_frg(frg.input_hz(), baudrate)?;
+
+            frg.set_frgdiv(0xff);
+            frg.set_frgmult(config.frg_mult);
+
+            Ok(Self::from_config(frg, &config))
+        }
+    }
 }
 
 #[cfg(feature = "845")]
@@ -74,77 +328,76 @@ mod target {
     use core::marker::PhantomData;
 
     use crate::{
+        clock::Frequency as _,
         syscon::{
             self,
             clock_source::{PeripheralClock, PeripheralClockSelector},
+            frg, FRG,
         },
         usart::state::AsyncMode,
     };
 
-    use super::{Clock, ClockSource};
+    use super::{BaudrateConfig, Clock, ClockError, ClockSource};
 
     impl Clock<syscon::IOSC, AsyncMode> {
         /// Create a new configuration with a specified baudrate
         ///
-        /// Searches for configuration values that lead to a baud rate that is
-        /// within 5% accuracy of the desired baudrate. Panics, if it can't find
-        /// such parameters.
+        /// Searches for the configuration that gets closest to the desired
+        /// baud rate, as described in [`BaudrateConfig::calculate`]. Returns
+        /// an error, if no configuration within 5% accuracy can be found.
         ///
-        /// Chooses the highest possibly oversampling value that will still give
-        /// the desired accuracy. Please note that if the oversampling value
-        /// gets too low, this can result in framing and noise errors when
-        /// receiving data.
+        /// If the oversampling value gets too low, this can result in framing
+        /// and noise errors when receiving data. If you need more control,
+        /// please use [`Clock::new`] in combination with an FRG.
         ///
-        /// Due to the aforementioned limitations, and because this methods is
-        /// relatively computationally expensive, it is recommended to only use
-        /// it during initialization, with known baud rates. If you need more
-        /// control, please use [`Clock::new`] in combination with an FRG.
+        /// Uses the frequency of the internal oscillator, as reported by
+        /// [`IOSC`].
         ///
-        /// Assumes the internal oscillator runs at 12 MHz.
-        pub fn new_with_baudrate(baudrate: u32) -> Self {
-            fn calculate_brgval(
-                desired_baudrate: u32,
-                osrval: u8,
-            ) -> (u16, u8) {
-                let iosc_frequency = 12_000_000;
-
-                let brgval = iosc_frequency
-                    / (desired_baudrate * (osrval + 1) as u32)
-                    - 1;
-                let resulting_baudrate =
-                    iosc_frequency / (brgval + 1) / (osrval as u32 + 1);
-
-                // This subtraction should never overflow. Due to rounding, the
-                // resulting baud rate is always going to be higher than the
-                // desired one.
-                let deviation_percent = (resulting_baudrate - desired_baudrate)
-                    * 100
-                    / desired_baudrate;
-
-                (brgval as u16, deviation_percent as u8)
-            }
-            fn search_parameters(baudrate: u32) -> (u16, u8) {
-                // Look for the highest `osrval` that will give us an accuracy
-                // within 5%.
-                for osrval in (0x4..=0xf).rev() {
-                    let (brgval, deviation_percent) =
-                        calculate_brgval(baudrate, osrval);
-                    if deviation_percent < 5 {
-                        return (brgval, osrval);
-                    }
-                }
-
-                panic!("Could not find parameters that are accurate within 5%");
-            }
-
-            let (brgval, osrval) = search_parameters(baudrate);
+        /// [`BaudrateConfig::calculate`]: struct.BaudrateConfig.html#method.calculate
+        /// [`IOSC`]: ../syscon/struct.IOSC.html
+        pub fn new_with_baudrate(baudrate: u32) -> Result<Self, ClockError> {
+            let config =
+                BaudrateConfig::calculate(syscon::IOSC::new().hz(), baudrate)?;
 
-            Self {
-                brgval,
-                osrval,
+            Ok(Self {
+                brgval: config.brgval,
+                osrval: config.osrval - 1,
                 _clock: PhantomData,
                 _mode: PhantomData,
-            }
+            })
+        }
+    }
+
+    impl<I> Clock<FRG<I>, AsyncMode>
+    where
+        I: frg::Instance,
+        FRG<I>: PeripheralClock,
+    {
+        /// Create the clock configuration for a given baud rate, using an FRG
+        ///
+        /// Computes the configuration as described in
+        /// [`BaudrateConfig::calculate_with_frg`], from the frequency of the
+        /// clock source that is selected for `frg`, and configures the
+        /// fractional generator accordingly. This changes the baud rate of
+        /// all other peripherals that use the same fractional generator.
+        ///
+        /// # Panics
+        ///
+        /// Panics, if no clock source is selected for `frg`, or if its clock
+        /// source is derived from a clock of unknown frequency.
+        ///
+        /// [`BaudrateConfig::calculate_with_frg`]: struct.BaudrateConfig.html#method.calculate_with_frg
+        pub fn from_baudrate_with_frg(
+            frg: &mut FRG<I>,
+            baudrate: u32,
+        ) -> Result<Self, ClockError> {
+            let config =
+                BaudrateConfig::calculate_with_frg(frg.input_hz(), baudrate)?;
+
+            frg.set_div(0xff);
+            frg.set_mult(config.frg_mult);
+
+            Ok(Self::from_config(frg, &config))
         }
     }
 
@@ -0,0 +1,178 @@
+use cortex_m::interrupt;
+
+use crate::{
+    dma::{
+        self,
+        transfer::state::{Ready, Started},
+    },
+    init_state,
+};
+
+use super::{
+    instances::Instance,
+    rx::Rx,
+    state::{Enabled, NoThrottle, SyncMode, Word},
+    tx::Tx,
+    USART,
+};
+
+/// A full-duplex USART/DMA transfer in synchronous mode
+///
+/// In synchronous mode, the USART receives a word for every word it sends,
+/// similar to an SPI peripheral. This `Transfer` wraps and manages two
+/// [`dma::Transfer`] structs under the hood, sending all words from a buffer
+/// and writing the received words back into it.
+///
+/// While the transfer is going on, SCLK only runs while words are being sent,
+/// so every received word corresponds to a sent one. The previous clock
+/// configuration is restored when the transfer has finished.
+///
+/// [`dma::Transfer`]: ../dma/struct.Transfer.html
+pub struct Transfer<State, I, W>
+where
+    I: Instance,
+    W: Word + 'static,
+{
+    usart: USART<I, Enabled<W, SyncMode>>,
+    buffer: &'static mut [W],
+    rx_transfer: RxTransfer<State, I, W>,
+    tx_transfer: TxTransfer<State, I, W>,
+    continuous_clock: bool,
+}
+
+type RxTransfer<State, I, W> = dma::Transfer<
+    State,
+    <I as Instance>::RxChannel,
+    Rx<I, Enabled<W, SyncMode>>,
+    dma::Buffer<W>,
+>;
+
+type TxTransfer<State, I, W> = dma::Transfer<
+    State,
+    <I as Instance>::TxChannel,
+    dma::Buffer<W>,
+    Tx<I, Enabled<W, SyncMode>, NoThrottle>,
+>;
+
+type Channel<C> = dma::Channel<C, init_state::Enabled>;
+
+impl<I, W> Transfer<Ready, I, W>
+where
+    I: Instance,
+    W: Word + dma::Word,
+{
+    pub(super) fn new(
+        usart: USART<I, Enabled<W, SyncMode>>,
+        buffer: &'static mut [W],
+        rx_channel: Channel<I::RxChannel>,
+        tx_channel: Channel<I::TxChannel>,
+    ) -> Self {
+        // Sound, as access to CTL is protected by a critical section, and we
+        // own the USART, so nobody else is reading from RXDAT.
+        let registers = unsafe { &*I::REGISTERS };
+
+        // Save the clock configuration, so it can be restored once the
+        // transfer has finished.
+        let continuous_clock = interrupt::free(|_| {
+            let continuous_clock = registers.ctl.read().cc().bit();
+
+            // Only clock while sending, so we don't receive any words that
+            // don't correspond to a word in the buffer.
+            registers.ctl.modify(|_, w| w.cc().clock_on_character());
+
+            continuous_clock
+        });
+
+        // Discard anything that was received before the transfer, as it would
+        // end up in the wrong place in the buffer otherwise.
+        while registers.stat.read().rxrdy().bit_is_set() {
+            registers.rxdatstat.read();
+        }
+        registers.stat.write(|w| w.overrunint().set_bit());
+
+        let ptr = buffer.as_mut_ptr();
+        let len = buffer.len();
+
+        // This is sound, since we know that the USART will not access the
+        // buffers concurrently, due to the way synchronous mode works:
+        // - The USART only receives a word while it sends one. The word to be
+        //   sent is read from the buffer before the word that is received at
+        //   the same time is written.
+        // - Once a word has been received, it will overwrite the word in the
+        //   buffer that was sent during the same clock cycles. At that point,
+        //   that part of the buffer will no longer be relevant for the sending
+        //   side.
+        let rx_buffer = unsafe { dma::Buffer::new(ptr, len) };
+        let tx_buffer = unsafe { dma::Buffer::new(ptr, len) };
+
+        let rx_transfer = dma::Transfer::new(rx_channel, Rx::new(), rx_buffer);
+        let tx_transfer = dma::Transfer::new(tx_channel, tx_buffer, Tx::new());
+
+        Self {
+            usart,
+            buffer,
+            rx_transfer,
+            tx_transfer,
+            continuous_clock,
+        }
+    }
+
+    /// Start the transfer
+    ///
+    /// Starts both DMA transfers that are part of this USART transfer.
+    pub fn start(self) -> Transfer<Started, I, W> {
+        Transfer {
+            usart: self.usart,
+            buffer: self.buffer,
+            rx_transfer: self.rx_transfer.start(),
+            tx_transfer: self.tx_transfer.start(),
+            continuous_clock: self.continuous_clock,
+        }
+    }
+}
+
+impl<I, W> Transfer<Started, I, W>
+where
+    I: Instance,
+    W: Word + dma::Word,
+{
+    /// Wait for the transfer to finish
+    ///
+    /// Waits until both underlying DMA transfers have finished.
+    #[allow(clippy::type_complexity)]
+    pub fn wait(
+        self,
+    ) -> (
+        USART<I, Enabled<W, SyncMode>>,
+        &'static mut [W],
+        Channel<I::RxChannel>,
+        Channel<I::TxChannel>,
+    ) {
+        let continuous_clock = self.continuous_clock;
+
+        let tx_payload = match self.tx_transfer.wait() {
+            Ok(payload) => payload,
+            // can't happen, as error type is `Void`
+            Err(_) => unreachable!(),
+        };
+        let rx_payload = match self.rx_transfer.wait() {
+            Ok(payload) => payload,
+            // can't happen, as error type is `Void`
+            Err(_) => unreachable!(),
+        };
+
+        // Sound, as access to CTL is protected by a critical section.
+        let registers = unsafe { &*I::REGISTERS };
+
+        interrupt::free(|_| {
+            registers.ctl.modify(|_, w| w.cc().bit(continuous_clock));
+        });
+
+        (
+            self.usart,
+            self.buffer,
+            rx_payload.channel,
+            tx_payload.channel,
+        )
+    }
+}
@@ -0,0 +1,594 @@
+//! LIN (Local Interconnect Network) support
+//!
+//! LIN is a single-wire bus used in automotive applications. All
+//! communication is organized in frames that are initiated by the master. A
+//! frame consists of a header, which is always sent by the master, and a
+//! response, which is sent either by the master or by one of the slaves.
+//!
+//! - The header consists of a break of at least 13 bit times, a sync byte
+//!   (`0x55`), and a protected identifier (see [`Pid`]).
+//! - The response consists of 1 to 8 data bytes, followed by a checksum (see
+//!   [`Checksum`]).
+//!
+//! [`Master`] and [`Slave`] implement the protocol on top of a [`USART`] that
+//! has been enabled in asynchronous mode with 8-bit words, which is the
+//! default configuration. Both need a timer to time the break and response
+//! timeouts. Any [`CountDown`] implementation whose `Time` can be converted
+//! from [`Microseconds`], like [`mrt::Channel`], can be used for this.
+//!
+//! Both expect to be connected to a LIN transceiver, which echoes all
+//! transmitted data back to the receiver. This echo is used to check whether
+//! transmitted bytes made it onto the bus unchanged.
+//!
+//! The frame encoding (protected identifiers, checksums, and timeouts) is
+//! available through methods and functions that don't access the hardware.
+//!
+//! # Example
+//!
+//! ``` rust
+//! use lpc8xx_hal::usart::lin::{Checksum, Pid};
+//!
+//! let pid = Pid::from_id(0x10).unwrap();
+//! assert_eq!(pid.to_byte(), 0x50);
+//!
+//! let checksum = Checksum::Enhanced.compute(pid, &[0x01, 0x02, 0x03]);
+//! assert_eq!(checksum, 0xa9);
+//! ```
+//!
+//! [`USART`]: ../struct.USART.html
+//! [`CountDown`]: ../../embedded_hal/timer/trait.CountDown.html
+//! [`Microseconds`]: ../../embedded_time/duration/units/struct.Microseconds.html
+//! [`mrt::Channel`]: ../../mrt/struct.Channel.html
+
+use core::convert::TryFrom;
+
+use embedded_hal::{
+    serial::{Read as _, Write as _},
+    timer::CountDown,
+};
+use embedded_time::duration::Microseconds;
+use nb::block;
+
+use super::{
+    instances::Instance,
+    rx,
+    state::{AsyncMode, Enabled},
+    USART,
+};
+
+/// The value of the sync byte that follows the break
+pub const SYNC: u8 = 0x55;
+
+/// The minimum length of a break, in bit times
+pub const BREAK_BITS: u32 = 13;
+
+/// The length of the break delimiter, in bit times
+pub const BREAK_DELIMITER_BITS: u32 = 1;
+
+/// The maximum number of data bytes in a frame
+pub const MAX_DATA_LEN: usize = 8;
+
+/// A protected identifier
+///
+/// Consists of a 6-bit frame identifier and two parity bits, as it is sent in
+/// the frame header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Pid(u8);
+
+impl Pid {
+    /// The highest valid frame identifier
+    pub const MAX_ID: u8 = 0x3f;
+
+    /// Create a protected identifier from a frame identifier
+    ///
+    /// Computes the parity bits for the frame identifier. Returns
+    /// [`Error::IdOutOfRange`], if `id` is larger than [`Pid::MAX_ID`].
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::usart::lin::Pid;
+    ///
+    /// assert_eq!(Pid::from_id(0x00).unwrap().to_byte(), 0x80);
+    /// assert_eq!(Pid::from_id(0x3c).unwrap().to_byte(), 0x3c);
+    /// assert_eq!(Pid::from_id(0x3d).unwrap().to_byte(), 0x7d);
+    /// assert!(Pid::from_id(0x40).is_err());
+    /// ```
+    ///
+    /// [`Error::IdOutOfRange`]: enum.Error.html#variant.IdOutOfRange
+    /// [`Pid::MAX_ID`]: #associatedconstant.MAX_ID
+    pub fn from_id(id: u8) -> Result<Self, Error> {
+        if id > Self::MAX_ID {
+            return Err(Error::IdOutOfRange);
+        }
+
+        let bit = |n: u8| (id >> n) & 0x1;
+
+        let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+        let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 0x1;
+
+        Ok(Self(id | p0 << 6 | p1 << 7))
+    }
+
+    /// Create a protected identifier from a received byte
+    ///
+    /// Returns [`Error::Parity`], if the parity bits don't match the frame
+    /// identifier.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::usart::lin::{Error, Pid};
+    ///
+    /// assert_eq!(Pid::from_byte(0xc1).unwrap().id(), 0x01);
+    /// assert_eq!(Pid::from_byte(0x01), Err(Error::Parity(0x01)));
+    /// ```
+    ///
+    /// [`Error::Parity`]: enum.Error.html#variant.Parity
+    pub fn from_byte(byte: u8) -> Result<Self, Error> {
+        let pid = Self::from_id(byte & Self::MAX_ID)?;
+
+        if pid.0 != byte {
+            return Err(Error::Parity(byte));
+        }
+
+        Ok(pid)
+    }
+
+    /// Return the frame identifier, without the parity bits
+    pub fn id(&self) -> u8 {
+        self.0 & Self::MAX_ID
+    }
+
+    /// Return the protected identifier, as it is sent on the bus
+    pub fn to_byte(&self) -> u8 {
+        self.0
+    }
+}
+
+/// The checksum model used for a frame
+///
+/// The checksum is the inverted eight-bit sum with carry over the data bytes
+/// (classic) or over the protected identifier and the data bytes (enhanced).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Checksum {
+    /// Classic checksum, as used by LIN 1.x
+    Classic,
+
+    /// Enhanced checksum, as used by LIN 2.x
+    ///
+    /// Frames with the diagnostic identifiers `0x3c` and `0x3d` always use the
+    /// classic checksum, as required by the LIN specification. This is taken
+    /// into account automatically.
+    Enhanced,
+}
+
+impl Checksum {
+    /// Compute the checksum of a frame
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use lpc8xx_hal::usart::lin::{Checksum, Pid};
+    ///
+    /// let pid = Pid::from_id(0x3c).unwrap();
+    /// let data = [0x7f, 0x06, 0xb2, 0x00, 0xff, 0x7f, 0xff, 0xff];
+    ///
+    /// // Diagnostic frames always use the classic checksum.
+    /// assert_eq!(Checksum::Classic.compute(pid, &data), 0x48);
+    /// assert_eq!(Checksum::Enhanced.compute(pid, &data), 0x48);
+    /// ```
+    pub fn compute(&self, pid: Pid, data: &[u8]) -> u8 {
+        let include_pid = match self {
+            Self::Classic => false,
+            Self::Enhanced => pid.id() < 0x3c,
+        };
+
+        let initial = if include_pid { pid.to_byte() as u16 } else { 0 };
+
+        let sum = data.iter().fold(initial, |sum, &byte| {
+            let sum = sum + byte as u16;
+            if sum > 0xff {
+                sum - 0xff
+            } else {
+                sum
+            }
+        });
+
+        !(sum as u8)
+    }
+}
+
+/// Convert a number of bit times into microseconds, rounding up
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::usart::lin;
+///
+/// assert_eq!(lin::bit_times_us(1, 19_200), 53);
+/// assert_eq!(lin::bit_times_us(lin::BREAK_BITS, 19_200), 678);
+/// ```
+pub fn bit_times_us(bits: u32, baud_rate: u32) -> u32 {
+    let us = (bits as u64 * 1_000_000).div_ceil(baud_rate as u64);
+    us as u32
+}
+
+/// The maximum duration of a frame header, in microseconds
+///
+/// The nominal duration of the header is 34 bit times. The LIN specification
+/// allows it to take up to 40% longer.
+pub fn header_timeout_us(baud_rate: u32) -> u32 {
+    bit_times_us(max_bits(34), baud_rate)
+}
+
+/// The maximum duration of a response, in microseconds
+///
+/// The nominal duration of a response with `data_len` data bytes is 10 bit
+/// times per data byte, plus 10 bit times for the checksum. The LIN
+/// specification allows it to take up to 40% longer.
+///
+/// # Example
+///
+/// ``` rust
+/// use lpc8xx_hal::usart::lin;
+///
+/// assert_eq!(lin::response_timeout_us(8, 19_200), 6_563);
+/// ```
+pub fn response_timeout_us(data_len: usize, baud_rate: u32) -> u32 {
+    bit_times_us(max_bits(10 * (data_len as u32 + 1)), baud_rate)
+}
+
+fn max_bits(nominal_bits: u32) -> u32 {
+    (nominal_bits * 14).div_ceil(10)
+}
+
+/// A LIN master
+///
+/// Sends frame headers, and either sends or receives the response. See
+/// [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct Master<I, Timer>(Bus<I, Timer>);
+
+impl<I, Timer> Master<I, Timer>
+where
+    I: Instance,
+    Timer: CountDown,
+    Timer::Time: TryFrom<Microseconds>,
+{
+    /// Create a LIN master
+    ///
+    /// `baud_rate` must be the baud rate the USART has been configured with.
+    /// It is used to compute the length of the break and the timeouts.
+    pub fn new(
+        usart: USART<I, Enabled<u8, AsyncMode>>,
+        timer: Timer,
+        baud_rate: u32,
+    ) -> Self {
+        Self(Bus {
+            usart,
+            timer,
+            baud_rate,
+        })
+    }
+
+    /// Send a frame header
+    ///
+    /// Sends the break, the sync byte, and the protected identifier. Usually
+    /// you want to use [`write_frame`] or [`read_frame`] instead, which also
+    /// take care of the response. Use this method directly for frames that
+    /// you don't expect a response to, or to handle the response yourself.
+    ///
+    /// [`write_frame`]: #method.write_frame
+    /// [`read_frame`]: #method.read_frame
+    pub fn send_header(&mut self, pid: Pid) -> Result<(), Error> {
+        self.0.send_header(pid)
+    }
+
+    /// Send a frame header, followed by a response
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `data` is 0 or larger than 8.
+    pub fn write_frame(
+        &mut self,
+        pid: Pid,
+        data: &[u8],
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        self.0.send_header(pid)?;
+        self.0.write_response(pid, data, checksum)
+    }
+
+    /// Send a frame header, then receive the response from a slave
+    ///
+    /// Fills `buffer` with the received data. Returns [`Error::Timeout`], if
+    /// the slave doesn't send a complete response in time.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 8.
+    ///
+    /// [`Error::Timeout`]: enum.Error.html#variant.Timeout
+    pub fn read_frame(
+        &mut self,
+        pid: Pid,
+        buffer: &mut [u8],
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        self.0.send_header(pid)?;
+        self.0.read_response(pid, buffer, checksum)
+    }
+
+    /// Return the USART and timer
+    pub fn free(self) -> (USART<I, Enabled<u8, AsyncMode>>, Timer) {
+        (self.0.usart, self.0.timer)
+    }
+}
+
+/// A LIN slave
+///
+/// Waits for frame headers from the master, and either sends or receives the
+/// response, depending on the frame. See [module documentation] for more
+/// information.
+///
+/// [module documentation]: index.html
+pub struct Slave<I, Timer>(Bus<I, Timer>);
+
+impl<I, Timer> Slave<I, Timer>
+where
+    I: Instance,
+    Timer: CountDown,
+    Timer::Time: TryFrom<Microseconds>,
+{
+    /// Create a LIN slave
+    ///
+    /// `baud_rate` must be the baud rate the USART has been configured with.
+    /// It is used to compute the timeouts.
+    pub fn new(
+        usart: USART<I, Enabled<u8, AsyncMode>>,
+        timer: Timer,
+        baud_rate: u32,
+    ) -> Self {
+        Self(Bus {
+            usart,
+            timer,
+            baud_rate,
+        })
+    }
+
+    /// Wait for a frame header
+    ///
+    /// Returns `WouldBlock`, until a break has been received. All data
+    /// received before the break is discarded. Once the break has been
+    /// received, this method blocks until the sync byte and protected
+    /// identifier have been received, or the header timeout has expired.
+    ///
+    /// After a header has been received, the application must decide, based on
+    /// the returned identifier, whether to call [`write_response`],
+    /// [`read_response`], or ignore the frame.
+    ///
+    /// [`write_response`]: #method.write_response
+    /// [`read_response`]: #method.read_response
+    pub fn read_header(&mut self) -> nb::Result<Pid, Error> {
+        loop {
+            match self.0.usart.rx.read() {
+                // A break is received as a zero with a missing stop bit.
+                Err(nb::Error::Other(rx::Error::Framing(0))) => break,
+                Err(nb::Error::WouldBlock) => {
+                    return Err(nb::Error::WouldBlock)
+                }
+                // Anything else is not part of a header.
+                _ => {}
+            }
+        }
+
+        self.0.start_timeout(header_timeout_us(self.0.baud_rate));
+
+        let sync = self.0.read()?;
+        if sync != SYNC {
+            return Err(nb::Error::Other(Error::Sync(sync)));
+        }
+
+        let pid = Pid::from_byte(self.0.read()?)?;
+
+        Ok(pid)
+    }
+
+    /// Send a response to the frame header that was last received
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `data` is 0 or larger than 8.
+    pub fn write_response(
+        &mut self,
+        pid: Pid,
+        data: &[u8],
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        self.0.write_response(pid, data, checksum)
+    }
+
+    /// Receive the response to the frame header that was last received
+    ///
+    /// Fills `buffer` with the received data. Returns [`Error::Timeout`], if
+    /// the sender doesn't send a complete response in time.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 8.
+    ///
+    /// [`Error::Timeout`]: enum.Error.html#variant.Timeout
+    pub fn read_response(
+        &mut self,
+        pid: Pid,
+        buffer: &mut [u8],
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        self.0.read_response(pid, buffer, checksum)
+    }
+
+    /// Return the USART and timer
+    pub fn free(self) -> (USART<I, Enabled<u8, AsyncMode>>, Timer) {
+        (self.0.usart, self.0.timer)
+    }
+}
+
+/// The parts that [`Master`] and [`Slave`] have in common
+struct Bus<I, Timer> {
+    usart: USART<I, Enabled<u8, AsyncMode>>,
+    timer: Timer,
+    baud_rate: u32,
+}
+
+impl<I, Timer> Bus<I, Timer>
+where
+    I: Instance,
+    Timer: CountDown,
+    Timer::Time: TryFrom<Microseconds>,
+{
+    fn send_header(&mut self, pid: Pid) -> Result<(), Error> {
+        // Discard anything that is still in the receive buffer, so it doesn't
+        // get confused with the echo of the header.
+        while !matches!(self.usart.rx.read(), Err(nb::Error::WouldBlock)) {}
+
+        block!(self.usart.tx.start_break()).unwrap();
+        self.delay(BREAK_BITS);
+        self.usart.tx.stop_break();
+        self.delay(BREAK_DELIMITER_BITS);
+
+        // The echo of the break is received as a zero with a missing stop bit.
+        self.start_timeout(header_timeout_us(self.baud_rate));
+        match self.read() {
+            Err(Error::Usart(rx::Error::Framing(0))) => {}
+            Err(Error::Timeout) => return Err(Error::Timeout),
+            _ => return Err(Error::Readback),
+        }
+
+        self.write_checked(SYNC)?;
+        self.write_checked(pid.to_byte())?;
+
+        Ok(())
+    }
+
+    fn write_response(
+        &mut self,
+        pid: Pid,
+        data: &[u8],
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        assert!(!data.is_empty() && data.len() <= MAX_DATA_LEN);
+
+        self.start_timeout(response_timeout_us(data.len(), self.baud_rate));
+
+        for &byte in data {
+            self.write_checked(byte)?;
+        }
+        self.write_checked(checksum.compute(pid, data))?;
+
+        Ok(())
+    }
+
+    fn read_response(
+        &mut self,
+        pid: Pid,
+        buffer: &mut [u8],
+        checksum: Checksum,
+    ) -> Result<(), Error> {
+        assert!(!buffer.is_empty() && buffer.len() <= MAX_DATA_LEN);
+
+        self.start_timeout(response_timeout_us(buffer.len(), self.baud_rate));
+
+        for byte in buffer.iter_mut() {
+            *byte = self.read()?;
+        }
+
+        let expected = checksum.compute(pid, buffer);
+        let received = self.read()?;
+        if received != expected {
+            return Err(Error::Checksum { expected, received });
+        }
+
+        Ok(())
+    }
+
+    /// Write a byte and check that its echo matches
+    ///
+    /// Expects the timer to have been started.
+    fn write_checked(&mut self, byte: u8) -> Result<(), Error> {
+        block!(self.usart.tx.write(byte)).unwrap();
+
+        if self.read()? != byte {
+            return Err(Error::Readback);
+        }
+
+        Ok(())
+    }
+
+    /// Read a byte, unless the timer expires first
+    ///
+    /// Expects the timer to have been started.
+    fn read(&mut self) -> Result<u8, Error> {
+        loop {
+            match self.usart.rx.read() {
+                Ok(byte) => return Ok(byte),
+                Err(nb::Error::Other(err)) => return Err(Error::Usart(err)),
+                Err(nb::Error::WouldBlock) => {}
+            }
+
+            if self.timer.wait().is_ok() {
+                return Err(Error::Timeout);
+            }
+        }
+    }
+
+    fn delay(&mut self, bits: u32) {
+        self.start_timeout(bit_times_us(bits, self.baud_rate));
+        block!(self.timer.wait()).unwrap();
+    }
+
+    fn start_timeout(&mut self, us: u32) {
+        let time = match Timer::Time::try_from(Microseconds(us)) {
+            Ok(time) => time,
+            Err(_) => panic!("LIN timing can't be represented by timer"),
+        };
+        self.timer.start(time);
+    }
+}
+
+/// A LIN error
+#[derive(Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A frame identifier larger than [`Pid::MAX_ID`] was specified
+    ///
+    /// [`Pid::MAX_ID`]: struct.Pid.html#associatedconstant.MAX_ID
+    IdOutOfRange,
+
+    /// The parity bits of a received protected identifier are wrong
+    Parity(u8),
+
+    /// The byte received after a break was not the sync byte
+    Sync(u8),
+
+    /// The checksum of a received response is wrong
+    Checksum {
+        /// The checksum computed from the received data
+        expected: u8,
+
+        /// The checksum that was actually received
+        received: u8,
+    },
+
+    /// A transmitted byte was not echoed back from the bus unchanged
+    ///
+    /// This usually means that another node was sending at the same time.
+    Readback,
+
+    /// A header or response didn't complete in time
+    Timeout,
+
+    /// The USART receiver reported an error
+    Usart(rx::Error<u8>),
+}
@@ -39,7 +39,7 @@
 //!     usart::Clock::new(&syscon.uartfrg, 0, 16)
 //! };
 //! #[cfg(feature = "845")]
-//! let clock_config = usart::Clock::new_with_baudrate(115200);
+//! let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
 //!
 //! let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
 //!     p.pins.pio0_0.into_swm_pin(),
@@ -71,6 +71,7 @@
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
 
 mod clock;
+mod dma;
 mod flags;
 mod instances;
 mod peripheral;
@@ -78,10 +79,12 @@ mod rx;
 mod settings;
 mod tx;
 
+pub mod lin;
 pub mod state;
 
 pub use self::{
-    clock::{Clock, ClockSource},
+    clock::{BaudrateConfig, Clock, ClockError, ClockSource},
+    dma::Transfer,
     flags::{Flag, Interrupts},
     instances::Instance,
     peripheral::USART,
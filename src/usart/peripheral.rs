@@ -7,13 +7,15 @@ use embedded_hal::{
 use void::Void;
 
 use crate::{
-    init_state::Disabled,
+    dma::{self, transfer::state::Ready},
+    init_state::{self, Disabled},
     pac::{usart0::cfg, NVIC},
     swm, syscon,
 };
 
 use super::{
     clock::{Clock, ClockSource},
+    dma::Transfer,
     flags::{Flag, Interrupts},
     instances::Instance,
     rx::{Error, Rx},
@@ -370,7 +372,7 @@ where
     /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
     /// # };
     /// # #[cfg(feature = "845")]
-    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     /// #
     /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
     /// #     p.pins.pio0_0.into_swm_pin(),
@@ -430,7 +432,7 @@ where
     /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
     /// # };
     /// # #[cfg(feature = "845")]
-    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     /// #
     /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
     /// #     p.pins.pio0_0.into_swm_pin(),
@@ -461,6 +463,30 @@ where
     }
 }
 
+impl<I, W> USART<I, Enabled<W, SyncMode>>
+where
+    I: Instance,
+    W: Word + dma::Word,
+{
+    /// Start a full-duplex transfer using DMA
+    ///
+    /// Sends all words in the provided buffer, writing the words received at
+    /// the same time back into it. This makes it possible to use the USART
+    /// like an additional SPI peripheral.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the length of `buffer` is 0 or larger than 1024.
+    pub fn transfer_all(
+        self,
+        buffer: &'static mut [W],
+        rx_channel: dma::Channel<I::RxChannel, init_state::Enabled>,
+        tx_channel: dma::Channel<I::TxChannel, init_state::Enabled>,
+    ) -> Transfer<Ready, I, W> {
+        Transfer::new(self, buffer, rx_channel, tx_channel)
+    }
+}
+
 impl<I, State> USART<I, State>
 where
     I: Instance,
@@ -102,6 +102,43 @@ where
         });
     }
 
+    /// Wait for the end of a break
+    ///
+    /// Returns `WouldBlock`, until a break condition has been detected on the
+    /// RX line and has ended again. This is useful for protocols that use a
+    /// break as a frame boundary.
+    ///
+    /// The hardware only detects a break once the RX line has been held low
+    /// for 16 bit times, and records the change in the DELTARXBRK flag, which
+    /// this method resets. If that flag was set before this method was first
+    /// called, the break that set it is reported.
+    ///
+    /// Shorter breaks are only visible as a framing error with a received
+    /// word of zero. Please note that the receiver also reports that framing
+    /// error for a break longer than 16 bit times, and it will be returned
+    /// from the next call to `read`.
+    pub fn wait_for_break(&mut self) -> nb::Result<(), Void> {
+        // Sound, as we're only reading from `stat`, and writing to it is
+        // stateless.
+        let usart = unsafe { &*I::REGISTERS };
+
+        if usart.stat.read().deltarxbrk().bit_is_clear() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        usart.stat.write(|w| w.deltarxbrk().set_bit());
+
+        // Only check RXBRK after resetting DELTARXBRK. Otherwise we might miss
+        // the end of a break that happens in between.
+        if usart.stat.read().rxbrk().bit_is_set() {
+            // The break has started, but not ended yet. The end of the break
+            // will set DELTARXBRK again.
+            return Err(nb::Error::WouldBlock);
+        }
+
+        Ok(())
+    }
+
     /// Query whether the provided flag is set
     ///
     /// Flags that need to be reset by software will be reset by this operation.
@@ -139,7 +176,7 @@ where
     /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
     /// # };
     /// # #[cfg(feature = "845")]
-    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     /// #
     /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
     /// #     p.pins.pio0_0.into_swm_pin(),
@@ -199,7 +236,7 @@ where
     /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
     /// # };
     /// # #[cfg(feature = "845")]
-    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     /// #
     /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
     /// #     p.pins.pio0_0.into_swm_pin(),
@@ -230,20 +267,25 @@ where
     }
 }
 
-impl<I, Mode> Rx<I, Enabled<u8, Mode>>
+impl<I, W, Mode> Rx<I, Enabled<W, Mode>>
 where
     I: Instance,
+    W: Word + dma::Word,
 {
     /// Reads until the provided buffer is full, using DMA
     ///
+    /// If the USART is configured for 9-bit words, the transfer uses 16-bit
+    /// words. In that case, the most significant bit of each received word is
+    /// the ninth data bit, which marks addresses in multi-drop mode.
+    ///
     /// # Panics
     ///
     /// Panics, if the length of `buffer` is 0 or larger than 1024.
     pub fn read_all(
         self,
-        buffer: &'static mut [u8],
+        buffer: &'static mut [W],
         channel: dma::Channel<I::RxChannel, init_state::Enabled>,
-    ) -> dma::Transfer<Ready, I::RxChannel, Self, &'static mut [u8]> {
+    ) -> dma::Transfer<Ready, I::RxChannel, Self, &'static mut [W]> {
         dma::Transfer::new(channel, self, buffer)
     }
 }
@@ -296,11 +338,13 @@ where
 
 impl<I, State> crate::private::Sealed for Rx<I, State> {}
 
-impl<I, Mode> dma::Source for Rx<I, Enabled<u8, Mode>>
+impl<I, W, Mode> dma::Source for Rx<I, Enabled<W, Mode>>
 where
     I: Instance,
+    W: Word + dma::Word,
 {
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         true
@@ -120,6 +120,66 @@ where
         function.unassign(pin, swm)
     }
 
+    /// Start sending a break
+    ///
+    /// While a break is being sent, the TX line is held in its active state
+    /// (low, unless the TX signal is inverted), which receivers detect as a
+    /// framing error or break condition.
+    ///
+    /// To avoid corrupting a character that is currently being transmitted,
+    /// this method first disables the transmitter and waits until it has
+    /// become idle. It returns `WouldBlock` until that has happened and the
+    /// break has started, and needs to be called repeatedly until it returns
+    /// `Ok`.
+    ///
+    /// The break continues until [`stop_break`] is called. Timing the length
+    /// of the break is the caller's responsibility.
+    ///
+    /// [`stop_break`]: #method.stop_break
+    pub fn start_break(&mut self) -> nb::Result<(), Void> {
+        // Sound, as we're only reading from `stat`, and access to CTL is
+        // protected by a critical section.
+        let usart = unsafe { &*I::REGISTERS };
+
+        interrupt::free(|_| {
+            let ctl = usart.ctl.read();
+
+            if ctl.txbrken().is_continous() {
+                return Ok(());
+            }
+
+            if ctl.txdis().is_enabled() {
+                usart.ctl.modify(|_, w| w.txdis().disabled());
+            }
+
+            if usart.stat.read().txdisstat().bit_is_clear() {
+                return Err(nb::Error::WouldBlock);
+            }
+
+            usart.ctl.modify(|_, w| {
+                w.txbrken().continous();
+                w.txdis().enabled()
+            });
+
+            Ok(())
+        })
+    }
+
+    /// Stop sending a break
+    ///
+    /// Ends a break that was started using [`start_break`]. Calling this
+    /// method while no break is being sent has no effect.
+    ///
+    /// [`start_break`]: #method.start_break
+    pub fn stop_break(&mut self) {
+        // Sound, as access to CTL is protected by a critical section.
+        let usart = unsafe { &*I::REGISTERS };
+
+        interrupt::free(|_| {
+            usart.ctl.modify(|_, w| w.txbrken().normal());
+        });
+    }
+
     /// Query whether the provided flag is set
     ///
     /// Flags that need to be reset by software will be reset by this operation.
@@ -157,7 +217,7 @@ where
     /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
     /// # };
     /// # #[cfg(feature = "845")]
-    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     /// #
     /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
     /// #     p.pins.pio0_0.into_swm_pin(),
@@ -217,7 +277,7 @@ where
     /// #     usart::Clock::new(&syscon.uartfrg, 0, 16)
     /// # };
     /// # #[cfg(feature = "845")]
-    /// # let clock_config = usart::Clock::new_with_baudrate(115200);
+    /// # let clock_config = usart::Clock::new_with_baudrate(115200).unwrap();
     /// #
     /// # let (u0_rxd, _) = swm.movable_functions.u0_rxd.assign(
     /// #     p.pins.pio0_0.into_swm_pin(),
@@ -313,20 +373,25 @@ where
     }
 }
 
-impl<I, Mode, Throttle> Tx<I, Enabled<u8, Mode>, Throttle>
+impl<I, W, Mode, Throttle> Tx<I, Enabled<W, Mode>, Throttle>
 where
     I: Instance,
+    W: Word + dma::Word,
 {
     /// Writes the provided buffer using DMA
     ///
+    /// If the USART is configured for 9-bit words, the transfer uses 16-bit
+    /// words. In that case, the most significant bit of each word is sent as
+    /// the ninth data bit, which marks addresses in multi-drop mode.
+    ///
     /// # Panics
     ///
     /// Panics, if the length of `buffer` is 0 or larger than 1024.
     pub fn write_all(
         self,
-        buffer: &'static [u8],
+        buffer: &'static [W],
         channel: dma::Channel<I::TxChannel, init_state::Enabled>,
-    ) -> dma::Transfer<Ready, I::TxChannel, &'static [u8], Self> {
+    ) -> dma::Transfer<Ready, I::TxChannel, &'static [W], Self> {
         dma::Transfer::new(channel, buffer, self)
     }
 }
@@ -391,11 +456,13 @@ where
 
 impl<I, State, Throttle> crate::private::Sealed for Tx<I, State, Throttle> {}
 
-impl<I, Mode, Throttle> dma::Dest for Tx<I, Enabled<u8, Mode>, Throttle>
+impl<I, W, Mode, Throttle> dma::Dest for Tx<I, Enabled<W, Mode>, Throttle>
 where
     I: Instance,
+    W: Word + dma::Word,
 {
     type Error = Void;
+    type Word = W;
 
     fn is_valid(&self) -> bool {
         true
@@ -26,18 +26,51 @@
 //! }
 //! ```
 //!
+//! Durations from `embedded_time` can be used instead of raw counts. Those are
+//! converted based on the frequency of the selected clock. Durations that
+//! exceed the 32-bit counter are supported by restarting the counter in
+//! [`WKT::wait`], as often as required.
+//!
+//! ``` no_run
+//! use lpc8xx_hal::{
+//!     prelude::*,
+//!     embedded_time::duration::Seconds,
+//!     Peripherals,
+//! };
+//!
+//! let mut p = Peripherals::take().unwrap();
+//!
+//! let mut syscon = p.SYSCON.split();
+//! let mut pmu = p.PMU.split();
+//! let mut timer = p.WKT.enable(&mut syscon.handle);
+//!
+//! let clock = pmu.low_power_clock.enable(&mut pmu.handle);
+//! timer.use_clock(&clock);
+//!
+//! // Wait for a week
+//! timer.start_duration(Seconds(7 * 24 * 60 * 60_u32)).unwrap();
+//!
+//! while let Err(nb::Error::WouldBlock) = timer.wait() {
+//!     // do stuff
+//! }
+//! ```
+//!
 //! Please refer to the [examples in the repository] for more example code.
 //!
 //! [examples in the repository]: https://github.com/lpc-rs/lpc8xx-hal/tree/master/examples
+//! [`WKT::wait`]: struct.WKT.html#method.wait
 
 use embedded_hal::timer;
+use embedded_time::{
+    duration::Duration, fixed_point::FixedPoint, fraction::Fraction,
+};
 use nb;
 use void::Void;
 
 use crate::{
-    init_state,
+    clock, init_state,
     pac::{self, wkt::ctrl},
-    pmu::LowPowerClock,
+    pmu::{LowPowerClock, WktClockInput},
     syscon::{self, IoscDerivedClock},
 };
 
@@ -57,6 +90,9 @@ use crate::{
 pub struct WKT<State = init_state::Enabled> {
     wkt: pac::WKT,
     _state: State,
+
+    clock_hz: Option<u32>,
+    extension: u64,
 }
 
 impl WKT<init_state::Disabled> {
@@ -64,6 +100,10 @@ impl WKT<init_state::Disabled> {
         WKT {
             wkt,
             _state: init_state::Disabled,
+
+            // The IRC/FRO-derived clock is selected after reset.
+            clock_hz: IoscDerivedClock::<init_state::Enabled>::FREQUENCY,
+            extension: 0,
         }
     }
 
@@ -87,6 +127,9 @@ impl WKT<init_state::Disabled> {
         WKT {
             wkt: self.wkt,
             _state: init_state::Enabled(()),
+
+            clock_hz: self.clock_hz,
+            extension: self.extension,
         }
     }
 }
@@ -112,6 +155,9 @@ impl WKT<init_state::Enabled> {
         WKT {
             wkt: self.wkt,
             _state: init_state::Disabled,
+
+            clock_hz: self.clock_hz,
+            extension: self.extension,
         }
     }
 
@@ -122,8 +168,11 @@ impl WKT<init_state::Enabled> {
     ///
     /// All clocks that can run the WKT implement a common trait. Please refer
     /// to [`wkt::Clock`] for a list of clocks that can be passed to this
-    /// method. Selecting an external clock via the WKTCLKIN pin is currently
-    /// not supported.
+    /// method.
+    ///
+    /// The frequency of the external clock input is not known at compile time.
+    /// If you select it using this method, durations can't be converted into
+    /// counts. Use [`WKT::use_clock`] to avoid this.
     ///
     /// # Limitations
     ///
@@ -132,6 +181,7 @@ impl WKT<init_state::Enabled> {
     /// disabling the clock while the timer is running.
     ///
     /// [`wkt::Clock`]: trait.Clock.html
+    /// [`WKT::use_clock`]: #method.use_clock
     pub fn select_clock<C>(&mut self)
     where
         C: Clock,
@@ -140,17 +190,117 @@ impl WKT<init_state::Enabled> {
             C::select(w);
             w
         });
+        self.clock_hz = C::FREQUENCY;
     }
-}
 
-impl timer::CountDown for WKT<init_state::Enabled> {
-    type Time = u32;
+    /// Select an enabled clock to run the self-wake-up timer
+    ///
+    /// Works like [`WKT::select_clock`], but requires the clock to be enabled,
+    /// and takes its frequency from the clock instance. This is required to
+    /// use durations with the external clock input.
+    ///
+    /// [`WKT::select_clock`]: #method.select_clock
+    pub fn use_clock<C>(&mut self, clock: &C)
+    where
+        C: Clock + clock::Frequency + clock::Enabled,
+    {
+        self.select_clock::<C>();
+        self.clock_hz = Some(clock.hz());
+    }
 
-    /// Starts a new count down
-    fn start<T>(&mut self, timeout: T)
+    /// Convert a duration into a count, based on the selected clock
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownFrequency`], if the frequency of the selected
+    /// clock is not known (see [`WKT::select_clock`]). Returns
+    /// [`Error::DurationTooLong`], if the count doesn't fit into 64 bits.
+    ///
+    /// [`Error::UnknownFrequency`]: enum.Error.html#variant.UnknownFrequency
+    /// [`Error::DurationTooLong`]: enum.Error.html#variant.DurationTooLong
+    /// [`WKT::select_clock`]: #method.select_clock
+    pub fn count_for<D>(&self, duration: D) -> Result<u64, Error>
     where
-        T: Into<Self::Time>,
+        D: Duration + FixedPoint,
+        u64: From<D::T>,
+    {
+        let hz = self.clock_hz.ok_or(Error::UnknownFrequency)?;
+        let count = duration
+            .to_generic::<u64>(Fraction::new(1, hz))
+            .map_err(|_| Error::DurationTooLong)?;
+
+        Ok(count.integer())
+    }
+
+    /// Start a count down for the given duration
+    ///
+    /// Converts the duration using [`WKT::count_for`] and starts the timer
+    /// using [`WKT::start_count`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`WKT::count_for`]. The timer is not started in
+    /// that case.
+    ///
+    /// [`WKT::count_for`]: #method.count_for
+    /// [`WKT::start_count`]: #method.start_count
+    pub fn start_duration<D>(&mut self, duration: D) -> Result<(), Error>
+    where
+        D: Duration + FixedPoint,
+        u64: From<D::T>,
     {
+        let count = self.count_for(duration)?;
+        self.start_count(count);
+        Ok(())
+    }
+
+    /// Start a count down with a 64-bit count
+    ///
+    /// If `count` doesn't fit into the 32-bit counter, the counter is started
+    /// with its maximum value, and restarted with the rest of the count by
+    /// [`WKT::wait`], whenever it expires. This means that [`WKT::wait`] must
+    /// be called after every expiration (for example from the interrupt
+    /// handler, or after waking up), for the count down to finish.
+    ///
+    /// [`WKT::wait`]: #method.wait
+    pub fn start_count(&mut self, count: u64) {
+        let (first, rest) = split_count(count);
+        self.extension = rest;
+        self.load(first);
+    }
+
+    /// Returns the remaining count until the count down finishes
+    ///
+    /// Includes the part of the count that will be loaded into the counter
+    /// later, if the count down was started with a count that doesn't fit into
+    /// the counter.
+    pub fn remaining(&self) -> u64 {
+        self.wkt.count.read().value().bits() as u64 + self.extension
+    }
+
+    /// Non-blockingly "waits" until the count down finishes
+    ///
+    /// If the count down was started with a count that doesn't fit into the
+    /// 32-bit counter, the counter is restarted with the rest of the count,
+    /// and `nb::Error::WouldBlock` is returned, until the full count has
+    /// expired.
+    pub fn wait(&mut self) -> nb::Result<(), Void> {
+        if self.wkt.ctrl.read().alarmflag().bit_is_clear() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if self.extension > 0 {
+            let (next, rest) = split_count(self.extension);
+            self.extension = rest;
+            self.load(next);
+
+            return Err(nb::Error::WouldBlock);
+        }
+
+        Ok(())
+    }
+
+    fn load(&mut self, count: u32) {
         // Either clearing the counter or writing a value to it resets the alarm
         // flag, so no reason to worry about that here.
 
@@ -159,18 +309,24 @@ impl timer::CountDown for WKT<init_state::Enabled> {
 
         // The counter has been cleared, which halts counting. Writing a new
         // count is perfectly safe.
-        self.wkt
-            .count
-            .write(|w| unsafe { w.value().bits(timeout.into()) });
+        self.wkt.count.write(|w| unsafe { w.value().bits(count) });
+    }
+}
+
+impl timer::CountDown for WKT<init_state::Enabled> {
+    type Time = u32;
+
+    /// Starts a new count down
+    fn start<T>(&mut self, timeout: T)
+    where
+        T: Into<Self::Time>,
+    {
+        self.start_count(timeout.into() as u64);
     }
 
     /// Non-blockingly "waits" until the count down finishes
     fn wait(&mut self) -> nb::Result<(), Void> {
-        if self.wkt.ctrl.read().alarmflag().bit_is_set() {
-            return Ok(());
-        }
-
-        Err(nb::Error::WouldBlock)
+        self.wait()
     }
 }
 
@@ -192,11 +348,33 @@ impl<State> WKT<State> {
     }
 }
 
+/// Returns the part of `count` that fits into the counter, and the rest
+fn split_count(count: u64) -> (u32, u64) {
+    let now = count.min(u32::MAX as u64);
+    (now as u32, count - now)
+}
+
+/// Indicates that a duration could not be converted into a count
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The frequency of the selected clock is not known
+    UnknownFrequency,
+
+    /// The duration is too long to be represented as a count
+    DurationTooLong,
+}
+
 /// A clock that is usable by the self-wake-up timer (WKT)
 ///
 /// This trait is implemented for all clocks that are supported by the WKT. The
 /// user shouldn't need to implement this trait themselves.
 pub trait Clock {
+    /// The frequency of the clock in Hz, if known at compile time
+    ///
+    /// This is an internal constant, to be used by the WKT API. It is exempt
+    /// from any guarantees of API stability.
+    const FREQUENCY: Option<u32>;
+
     /// Internal method to select the clock as the clock source for the WKT
     ///
     /// This is an internal method, to be called by the WKT API. Users generally
@@ -205,7 +383,19 @@ pub trait Clock {
     fn select(w: &mut ctrl::W);
 }
 
+/// A clock that keeps running in deep-sleep and power-down modes
+///
+/// Only these clocks can be used to wake up the system from deep-sleep or
+/// power-down mode using the WKT. See [`sleep::DeepSleep`] and
+/// [`sleep::PowerDown`].
+///
+/// [`sleep::DeepSleep`]: ../sleep/struct.DeepSleep.html
+/// [`sleep::PowerDown`]: ../sleep/struct.PowerDown.html
+pub trait LowPowerCapable: Clock {}
+
 impl<State> Clock for IoscDerivedClock<State> {
+    const FREQUENCY: Option<u32> = Some(750_000);
+
     fn select(w: &mut ctrl::W) {
         w.sel_extclk().internal();
         target::select_internal_oscillator(w);
@@ -213,11 +403,25 @@ impl<State> Clock for IoscDerivedClock<State> {
 }
 
 impl<State> Clock for LowPowerClock<State> {
+    const FREQUENCY: Option<u32> = Some(10_000);
+
     fn select(w: &mut ctrl::W) {
         w.sel_extclk().internal().clksel().low_power_clock();
     }
 }
 
+impl<State> LowPowerCapable for LowPowerClock<State> {}
+
+impl<State> Clock for WktClockInput<State> {
+    const FREQUENCY: Option<u32> = None;
+
+    fn select(w: &mut ctrl::W) {
+        w.sel_extclk().external();
+    }
+}
+
+impl<State> LowPowerCapable for WktClockInput<State> {}
+
 #[cfg(feature = "82x")]
 mod target {
     pub fn select_internal_oscillator(w: &mut crate::pac::wkt::ctrl::W) {